@@ -0,0 +1,144 @@
+use crate::phases::now_sec;
+use crate::*;
+use near_sdk::near_bindgen;
+
+/// Price starts at `start_price` when the phase starts and drops by `decay_step`
+/// every `interval_sec`, never below `floor_price`.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct DutchAuction {
+    pub start_price: U128,
+    pub floor_price: U128,
+    pub decay_step: U128,
+    pub interval_sec: u64,
+    /// Refund buyers down to the clearing price, the lowest price paid in the phase.
+    pub rebates: bool,
+}
+
+impl DutchAuction {
+    pub(crate) fn assert_valid(&self) {
+        assert!(self.interval_sec > 0, "ERR_WRONG_AUCTION:INTERVAL");
+        assert!(self.floor_price.0 <= self.start_price.0, "ERR_WRONG_AUCTION:PRICE");
+    }
+
+    pub(crate) fn price_at(&self, elapsed_sec: u64) -> Balance {
+        let steps = (elapsed_sec / self.interval_sec) as u128;
+        let decay = self.decay_step.0.saturating_mul(steps);
+        self.start_price.0.saturating_sub(decay).max(self.floor_price.0)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AuctionPrice {
+    pub phase: String,
+    pub current: U128,
+    /// `None` once the floor is reached.
+    pub next: Option<U128>,
+    pub next_at: Option<u64>,
+}
+
+impl Contract {
+    pub(crate) fn internal_phase(&self, name: &str) -> Option<Phase> {
+        self.internal_phases().into_iter().find(|phase| phase.name == name)
+    }
+
+    /// Records a rebate-eligible purchase and returns the part of `paid` that is held
+    /// back until rebates are claimed. The first purchase fixes the floor of the phase,
+    /// so rebates stay claimable if the phase is changed or removed later.
+    pub(crate) fn internal_record_auction_purchase(
+        &mut self,
+        phase: &Phase,
+        buyer_id: &AccountId,
+        price: Balance,
+        amount: u32,
+    ) -> Balance {
+        let auction = match &phase.dutch_auction {
+            Some(auction) if auction.rebates => auction,
+            _ => return 0,
+        };
+        if self.auction_floors.get(&phase.name).is_none() {
+            self.auction_floors.insert(&phase.name, &auction.floor_price.0);
+        }
+        let key = (phase.name.clone(), buyer_id.clone());
+        let (paid, bought) = self.auction_paid.get(&key).unwrap_or_default();
+        self.auction_paid.insert(&key, &(paid + price * amount as u128, bought + amount));
+        let clearing_price = self.clearing_prices.get(&phase.name).unwrap_or(price);
+        self.clearing_prices.insert(&phase.name, &clearing_price.min(price));
+        (price - auction.floor_price.0) * amount as u128
+    }
+
+    /// Whether the auction of `phase_name` ended, a removed phase is over.
+    fn internal_auction_finished(&self, phase_name: &str) -> bool {
+        let phase = match self.internal_phase(phase_name) {
+            Some(phase) => phase,
+            None => return true,
+        };
+        let sold_out = match phase.supply_cap {
+            Some(cap) => self.phase_sold.get(&phase.name).unwrap_or_default() >= cap,
+            None => false,
+        };
+        (phase.end != 0 && now_sec() >= phase.end) || sold_out
+    }
+
+    /// Rebate owed to `account_id` and the rest of its held back payment due as proceeds.
+    fn internal_rebate(&self, phase_name: &str, account_id: &AccountId) -> (Balance, Balance) {
+        let key = (phase_name.to_string(), account_id.clone());
+        let (paid, bought) = self.auction_paid.get(&key).unwrap_or_default();
+        if paid == 0 {
+            return (0, 0);
+        }
+        let floor_price = self.auction_floors.get(&key.0).unwrap();
+        // only minted passes are recorded, unlike the wallet counters reserved on purchase
+        let bought = bought as u128;
+        let clearing_price = self.clearing_prices.get(&key.0).unwrap_or_default();
+        let rebate = paid - clearing_price * bought;
+        (rebate, paid - floor_price * bought - rebate)
+    }
+
+    /// Asserts `phase` keeps the rebate floor fixed by its first purchase, if any.
+    pub(crate) fn assert_auction_unchanged(&self, phase: &Phase) {
+        if let Some(floor_price) = self.auction_floors.get(&phase.name) {
+            assert!(
+                matches!(&phase.dutch_auction, Some(auction)
+                    if auction.rebates && auction.floor_price.0 == floor_price),
+                "ERR_WRONG_AUCTION:STARTED"
+            );
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Current and next price of the active phase if it is a Dutch auction.
+    pub fn auction_price(&self) -> Option<AuctionPrice> {
+        let phase = self.internal_current_phase()?;
+        let auction = phase.dutch_auction.as_ref()?;
+        let elapsed = now_sec() - phase.start;
+        let current = auction.price_at(elapsed);
+        let next_at = phase.start + (elapsed / auction.interval_sec + 1) * auction.interval_sec;
+        let next = auction.price_at(next_at - phase.start);
+        let next_ends_phase = phase.end != 0 && next_at >= phase.end;
+        let (next, next_at) = match next < current && !next_ends_phase {
+            true => (Some(next.into()), Some(next_at)),
+            false => (None, None),
+        };
+        Some(AuctionPrice { phase: phase.name, current: current.into(), next, next_at })
+    }
+
+    pub fn rebate_of(&self, phase_name: String, account_id: AccountId) -> U128 {
+        self.internal_rebate(&phase_name, &account_id).0.into()
+    }
+
+    /// Pay out the rebate of `account_id` once the auction is over, the held back
+    /// rest is paid out as proceeds. Anyone can settle on behalf of a buyer.
+    pub fn claim_rebate(&mut self, phase_name: String, account_id: AccountId) -> U128 {
+        assert!(self.internal_auction_finished(&phase_name), "ERR_AUCTION_NOT_FINISHED");
+        let (rebate, proceeds) = self.internal_rebate(&phase_name, &account_id);
+        assert!(rebate + proceeds > 0, "ERR_NOTHING_TO_CLAIM");
+        self.auction_paid.remove(&(phase_name, account_id.clone()));
+        if rebate > 0 {
+            Promise::new(account_id).transfer(rebate);
+        }
+        self.internal_pay_proceeds(None, proceeds);
+        rebate.into()
+    }
+}
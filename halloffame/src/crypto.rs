@@ -1,23 +1,96 @@
+use crate::phases::now_sec;
 use crate::*;
-use std::convert::TryInto;
 
-use near_sdk::env::keccak256;
-use secp256k1;
+/// Curve of `signer_pk`: a hex compressed secp256k1 key signing the keccak256 of the
+/// payload, or a NEAR `ed25519:` base58 key signing the payload itself.
+#[derive(
+    BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug,
+)]
+pub enum KeyType {
+    Secp256k1,
+    Ed25519,
+}
 
-impl Contract {
-    pub fn verify_signature(&self, pk: String, signature: String, payload: String) -> bool {
-        let mut pk_slice: [u8; 33] = [0; 33];
-        hex::decode_to_slice(pk, &mut pk_slice).expect("ERR_WRONG_PKEY:HEX");
-        let public_key =
-            secp256k1::PublicKey::parse_compressed(&pk_slice).expect("ERR_WRONG_PKEY:PARSE");
+impl KeyType {
+    /// Key type of `pk`, panics if it is not a valid key of either type.
+    pub(crate) fn of(pk: &str) -> KeyType {
+        if pk.starts_with("ed25519:") {
+            signatures::parse_ed25519(pk);
+            return KeyType::Ed25519;
+        }
+        signatures::parse_secp256k1(pk);
+        KeyType::Secp256k1
+    }
+}
+
+/// Allowlist permission issued off-chain by a signer key for one phase of one deployment.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AllowlistPermit {
+    pub contract_id: AccountId,
+    pub phase: String,
+    pub permitted_amount: u32,
+    /// Currency of `price`: the fungible token paid with or `None` for NEAR.
+    pub token_id: Option<AccountId>,
+    /// Highest price per pass the permit is valid for, in `token_id`.
+    pub price: U128,
+    /// Unix seconds, like phase and signer key windows.
+    pub expires_at: u64,
+}
 
-        let hash = keccak256(payload.as_bytes());
-        let msg = secp256k1::Message::parse(&hash.try_into().unwrap());
+impl AllowlistPermit {
+    /// Fields joined by `:`, which account ids and phase names can't contain.
+    /// The currency is `near` or the token account id.
+    pub fn payload(&self, account_id: &AccountId) -> String {
+        format!(
+            "{}:{}:{}:{}:{}:{}:{}",
+            self.contract_id,
+            self.phase,
+            account_id,
+            self.permitted_amount,
+            self.price.0,
+            self.token_id.as_ref().map_or("near", |token_id| token_id.as_str()),
+            self.expires_at
+        )
+    }
+
+    /// Asserts the permit prices passes in `token_id`, `None` for NEAR.
+    pub(crate) fn assert_paid_in(&self, token_id: Option<&AccountId>) {
+        assert!(self.token_id.as_ref() == token_id, "ERR_WRONG_SIG:CURRENCY");
+    }
+}
 
-        let sign_vec = hex::decode(signature).expect("ERR_WRONG_SIG:HEX");
-        assert_eq!(sign_vec.len(), 64, "ERR_WRONG_SIG:SIZE");
-        let sign = secp256k1::Signature::parse(&sign_vec.try_into().unwrap());
+impl Contract {
+    /// Asserts `permit` is signed for `buyer_id`, this contract and `phase`, is not
+    /// expired and allows paying `cost` for `amount` passes.
+    pub(crate) fn assert_valid_permit(
+        &self,
+        phase: &Phase,
+        buyer_id: &AccountId,
+        permit: &AllowlistPermit,
+        signature: String,
+        amount: u32,
+        cost: Balance,
+    ) {
+        assert!(permit.contract_id == env::current_account_id(), "ERR_WRONG_SIG:CONTRACT");
+        assert!(permit.phase == phase.name, "ERR_WRONG_SIG:PHASE");
+        assert!(permit.expires_at > now_sec(), "ERR_WRONG_SIG:EXPIRED");
+        assert!(
+            self.internal_verify_signers(signature, permit.payload(buyer_id)),
+            "ERR_WRONG_SIG:PAYLOAD"
+        );
+        assert!(cost <= permit.price.0 * amount as u128, "ERR_WRONG_SIG:PRICE");
+    }
 
-        secp256k1::verify(&msg, &sign, &public_key)
+    pub fn verify_signature(
+        &self,
+        key_type: KeyType,
+        pk: String,
+        signature: String,
+        payload: String,
+    ) -> bool {
+        match key_type {
+            KeyType::Secp256k1 => signatures::verify_secp256k1(&pk, &signature, &payload),
+            KeyType::Ed25519 => signatures::verify_ed25519(&pk, &signature, &payload),
+        }
     }
 }
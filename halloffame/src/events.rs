@@ -0,0 +1,16 @@
+use crate::*;
+use near_sdk::serde_json::{json, Value};
+
+pub const EVENT_STANDARD: &str = "halloffame";
+pub const EVENT_VERSION: &str = "1.0.0";
+
+/// Logs a NEP-297 event of the sale.
+pub(crate) fn emit_event(event: &str, data: Value) {
+    let event = json!({
+        "standard": EVENT_STANDARD,
+        "version": EVENT_VERSION,
+        "event": event,
+        "data": [data],
+    });
+    env::log_str(&format!("EVENT_JSON:{}", event));
+}
@@ -1,5 +1,12 @@
 use crate::*;
 
+/// Result of `nft_mints`.
+#[derive(Serialize, Deserialize)]
+pub struct MintedTokens {
+    pub tokens: Vec<Token>,
+    pub storage_cost: U128,
+}
+
 /// external contract calls
 #[ext_contract(ext_nft)]
 trait NonFungibleToken {
@@ -22,5 +29,10 @@ trait NonFungibleToken {
 
     // view method
     fn nft_token(&self, token_id: String) -> Option<Token>;
-    fn nft_mints(&mut self, receiver_id: String, amount: u32) -> Vec<Token>;
+    fn nft_mints(&mut self, receiver_id: String, amount: u32) -> MintedTokens;
+}
+
+#[ext_contract(ext_ft)]
+trait FungibleToken {
+    fn ft_transfer(&mut self, receiver_id: AccountId, amount: U128, memo: Option<String>);
 }
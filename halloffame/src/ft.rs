@@ -0,0 +1,177 @@
+use crate::*;
+use near_contract_standards::fungible_token::receiver::FungibleTokenReceiver;
+use near_sdk::{assert_one_yocto, near_bindgen, serde_json, PromiseOrValue};
+
+/// `msg` of `ft_transfer_call`, the arguments of `sacrifice`.
+#[derive(Serialize, Deserialize)]
+pub struct SacrificeMsg {
+    pub amount: u32,
+    pub permit: Option<AllowlistPermit>,
+    pub signature: Option<String>,
+}
+
+impl Contract {
+    fn internal_credit_mint_storage(&mut self, account_id: &AccountId, amount: Balance) {
+        let balance = self.ft_mint_storage.get(account_id).unwrap_or_default();
+        self.ft_mint_storage.insert(account_id, &(balance + amount));
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Accept `token_id` as payment at a flat `price` per pass, `None` stops accepting it.
+    /// The price doesn't follow the phase `price` or `price_tiers`, passes bought with
+    /// tokens still count towards the tiers and caps. Auctions only take NEAR.
+    pub fn set_ft_price(&mut self, token_id: AccountId, price: Option<U128>) {
+        self.assert_owner_or_operator();
+        match price {
+            Some(price) => {
+                assert!(price.0 > 0, "ERR_WRONG_PRICE");
+                self.ft_prices.insert(&token_id, &price.0);
+            }
+            None => {
+                self.ft_prices.remove(&token_id);
+            }
+        }
+    }
+
+    pub fn ft_prices(&self) -> Vec<(AccountId, U128)> {
+        self.ft_prices.iter().map(|(token_id, price)| (token_id, price.into())).collect()
+    }
+
+    /// Prepay in NEAR the storage of passes `account_id` buys with tokens,
+    /// by default the predecessor. Returns the balance.
+    #[payable]
+    pub fn deposit_mint_storage(&mut self, account_id: Option<AccountId>) -> U128 {
+        let account_id = account_id.unwrap_or_else(env::predecessor_account_id);
+        let initial_storage_usage = env::storage_usage();
+        let balance = self.ft_mint_storage.get(&account_id).unwrap_or_default();
+        self.ft_mint_storage.insert(&account_id, &balance);
+        // the balance entry itself is paid from the deposit
+        let storage_cost =
+            (env::storage_usage() - initial_storage_usage) as Balance * env::storage_byte_cost();
+        assert!(env::attached_deposit() >= storage_cost, "ERR_NOT_ENOUGH");
+        let balance = balance + env::attached_deposit() - storage_cost;
+        self.ft_mint_storage.insert(&account_id, &balance);
+        balance.into()
+    }
+
+    /// Withdraw the unused storage prepayment of the caller.
+    #[payable]
+    pub fn withdraw_mint_storage(&mut self) -> Promise {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let balance = self.ft_mint_storage.get(&account_id).unwrap_or_default();
+        assert!(balance > 0, "ERR_NOTHING_TO_WITHDRAW");
+        self.ft_mint_storage.insert(&account_id, &0);
+        Promise::new(account_id).transfer(balance)
+    }
+
+    pub fn mint_storage_balance_of(&self, account_id: AccountId) -> U128 {
+        self.ft_mint_storage.get(&account_id).unwrap_or_default().into()
+    }
+
+    /// Returns the part of `paid` to refund, all of it if minting failed. The unused
+    /// storage prepayment goes back to the balance of the buyer.
+    #[private]
+    pub fn callback_on_ft_mints(
+        &mut self,
+        token_id: AccountId,
+        purchase: Purchase,
+        paid: U128,
+    ) -> U128 {
+        let Purchase { buyer_id, phase_name, public, amount: desired_amount, cost } = purchase;
+        assert_eq!(env::promise_results_count(), 1);
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Failed => {
+                self.internal_release_sale(&buyer_id, &phase_name, public, desired_amount);
+                self.internal_credit_mint_storage(&buyer_id, mint_deposit(desired_amount));
+                paid
+            }
+            PromiseResult::Successful(val) => {
+                let MintedTokens { tokens, storage_cost } =
+                    serde_json::from_slice(&val).expect("ERR_WRONG_VAL_RECEIVED");
+                // nft refunded whatever part of the mint deposit it didn't use for storage
+                self.internal_credit_mint_storage(
+                    &buyer_id,
+                    mint_deposit(desired_amount) - storage_cost.0,
+                );
+                let actual_amount = tokens.len() as u32;
+                let cost = cost.0 * actual_amount as u128 / desired_amount as u128;
+                self.internal_release_sale(
+                    &buyer_id,
+                    &phase_name,
+                    public,
+                    desired_amount - actual_amount,
+                );
+                self.internal_pay_proceeds(Some(token_id), cost);
+                (paid.0 - cost).into()
+            }
+        }
+    }
+}
+
+#[near_bindgen]
+impl FungibleTokenReceiver for Contract {
+    /// Buy passes for `sender_id` with a whitelisted token, `msg` is a `SacrificeMsg`.
+    /// Storage is paid from the `deposit_mint_storage` balance of `sender_id`,
+    /// unused tokens are refunded.
+    fn ft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        amount: U128,
+        msg: String,
+    ) -> PromiseOrValue<U128> {
+        let token_id = env::predecessor_account_id();
+        let price = self.ft_prices.get(&token_id).expect("ERR_TOKEN_NOT_ACCEPTED");
+        let SacrificeMsg { amount: passes, permit, signature } =
+            serde_json::from_str(&msg).expect("ERR_WRONG_MSG");
+        let phase = self.internal_current_phase().expect("ERR_NOT_STARTED");
+        assert!(phase.dutch_auction.is_none(), "ERR_AUCTION_NEAR_ONLY");
+        assert!(
+            env::prepaid_gas()
+                >= GAS_FOR_NFT_MINTS
+                    + GAS_FOR_NFT_MINT_CALL * passes.into()
+                    + GAS_FOR_RESOLVE_TRANSFER
+                    + GAS_FOR_SACRIFICE,
+            "ERR_NOT_ENOUGH_GAS"
+        );
+
+        if let Some(permit) = &permit {
+            permit.assert_paid_in(Some(&token_id));
+        }
+        let cost = price * passes as u128;
+        let storage_usage = env::storage_usage();
+        self.internal_check_purchase(&phase, &sender_id, passes, cost, permit, signature);
+        assert!(amount.0 >= cost, "ERR_NOT_ENOUGH");
+        let storage_cost = mint_deposit(passes)
+            + (env::storage_usage() - storage_usage) as u128 * env::storage_byte_cost();
+        let storage_balance = self.ft_mint_storage.get(&sender_id).unwrap_or_default();
+        assert!(storage_balance >= storage_cost, "ERR_STORAGE_NOT_PREPAID");
+        self.ft_mint_storage.insert(&sender_id, &(storage_balance - storage_cost));
+
+        ext_nft::nft_mints(
+            sender_id.to_string(),
+            passes,
+            self.nft_account_id.clone(),
+            mint_deposit(passes),
+            env::prepaid_gas() - GAS_FOR_SACRIFICE - GAS_FOR_RESOLVE_TRANSFER,
+        )
+        .then(ext_halloffame::callback_on_ft_mints(
+            token_id,
+            Purchase {
+                buyer_id: sender_id,
+                phase_name: phase.name,
+                public: !phase.allowlist,
+                amount: passes,
+                cost: cost.into(),
+            },
+            amount,
+            env::current_account_id(),
+            0,
+            GAS_FOR_RESOLVE_TRANSFER,
+        ))
+        .into()
+    }
+}
@@ -1,18 +1,37 @@
 use near_contract_standards::non_fungible_token::Token;
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
-    collections::{UnorderedMap, UnorderedSet},
+    collections::{LookupMap, UnorderedMap, UnorderedSet},
     env, ext_contract,
     json_types::U128,
     near_bindgen, AccountId, Balance, BorshStorageKey, Gas, PanicOnDefault, Promise, PromiseResult,
 };
 use serde::{Deserialize, Serialize};
 
+pub use crate::auction::{AuctionPrice, DutchAuction};
+pub use crate::crypto::{AllowlistPermit, KeyType};
 pub use crate::external::*;
+pub use crate::ft::SacrificeMsg;
+use crate::migrate::ContractV1;
+use crate::phases::now_sec;
+pub use crate::phases::Phase;
+pub use crate::proceeds::Proceeds;
+pub use crate::revenue::Beneficiary;
+pub use crate::signers::SignerKey;
+pub use crate::tiers::PriceTier;
 pub use crate::utils::*;
+mod auction;
 mod crypto;
+mod events;
 mod external;
+mod ft;
+mod migrate;
 mod owner;
+mod phases;
+mod proceeds;
+mod revenue;
+mod signers;
+mod tiers;
 mod utils;
 mod web4;
 
@@ -28,18 +47,46 @@ pub struct Contract {
     private_sale_timestamp: u64,
     open_sale_timestamp: u64,
     price_in_yocto: Balance,
+    phases: Vec<Phase>,
+    phase_sold: LookupMap<String, u32>,
+    phase_wallet_sold: LookupMap<(String, AccountId), u32>,
+    public_wallet_cap: Option<u32>,
+    transaction_cap: Option<u32>,
+    public_allocation: Option<u32>,
+    public_sold: u32,
+    auction_paid: LookupMap<(String, AccountId), (Balance, u32)>,
+    clearing_prices: LookupMap<String, Balance>,
+    auction_floors: LookupMap<String, Balance>,
+    price_tiers: Vec<PriceTier>,
+    total_sold: u32,
+    ft_prices: UnorderedMap<AccountId, Balance>,
+    ft_mint_storage: LookupMap<AccountId, Balance>,
+    revenue_split: Vec<Beneficiary>,
+    proceeds: LookupMap<AccountId, Vec<Proceeds>>,
+    signer_key_type: KeyType,
+    signer_keys: UnorderedMap<String, SignerKey>,
 }
 
 #[derive(BorshSerialize, BorshStorageKey)]
 enum StorageKey {
     Sold,
     Operator,
+    PhaseSold,
+    PhaseWalletSold,
+    AuctionPaid,
+    ClearingPrices,
+    FtPrices,
+    Proceeds,
+    SignerKeys,
+    AuctionFloors,
+    FtMintStorage,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct Config {
     pub nft_account_id: AccountId,
     pub signer_pk: Option<String>,
+    pub signer_key_type: KeyType,
     pub owner_id: AccountId,
     pub private_sale_timestamp: u64,
     pub open_sale_timestamp: u64,
@@ -47,6 +94,31 @@ pub struct Config {
     pub price_in_yocto: U128,
     pub stage: String,
     pub motivation: String,
+    pub active_phase: Option<Phase>,
+    pub upcoming_phases: Vec<Phase>,
+    /// Limits of phases with `public_limits` and without their own, `None` is unlimited.
+    pub public_wallet_cap: Option<u32>,
+    pub transaction_cap: Option<u32>,
+    pub public_allocation: Option<u32>,
+    pub public_sold: u32,
+    pub price_tiers: Vec<PriceTier>,
+    /// Passes left at the current tier price, `None` without tiers or once sold out.
+    pub tier_remaining: Option<u32>,
+    pub total_sold: u32,
+}
+
+/// Settings changed by `sudo_config`, the ones left out are kept. Timestamps are in seconds.
+#[derive(Serialize, Deserialize, Default)]
+pub struct ConfigUpdate {
+    pub nft_account_id: Option<AccountId>,
+    pub treasury_id: Option<AccountId>,
+    pub price_in_yocto: Option<U128>,
+    pub private_sale_timestamp: Option<u32>,
+    pub open_sale_timestamp: Option<u32>,
+    pub signer_pk: Option<String>,
+    pub public_wallet_cap: Option<u32>,
+    pub transaction_cap: Option<u32>,
+    pub public_allocation: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -55,6 +127,17 @@ pub struct Status {
     pub sold: u32,
 }
 
+/// Passes reserved by a purchase, passed to the callback of the `nft_mints` call.
+#[derive(Serialize, Deserialize)]
+pub struct Purchase {
+    pub buyer_id: AccountId,
+    pub phase_name: String,
+    /// Whether the passes count towards the public allocation.
+    pub public: bool,
+    pub amount: u32,
+    pub cost: U128,
+}
+
 macro_rules! update_if_exists {
     ($self:tt, $l:tt) => {
         if let Some($l) = $l {
@@ -72,30 +155,133 @@ macro_rules! update_if_exists {
 trait Contract {
     fn callback_on_nft_mints(
         &mut self,
-        price: Balance,
+        purchase: Purchase,
         attached_deposit: Balance,
-        desired_amount: u32,
     ) -> Vec<Token>;
+    fn callback_on_ft_mints(&mut self, token_id: AccountId, purchase: Purchase, paid: U128)
+        -> U128;
+    fn callback_on_withdraw(
+        &mut self,
+        account_id: AccountId,
+        token_id: Option<AccountId>,
+        amount: U128,
+    ) -> bool;
 }
 
-const MINT_COST: u128 = 10_u128.pow(20) * 80;
+impl Contract {
+    /// Asserts the purchase of `amount` passes for `cost` is allowed in `phase` and
+    /// reserves them in the sale counters, so concurrent purchases can't oversell.
+    /// Passes that fail to mint are given back with `internal_release_sale`.
+    pub(crate) fn internal_check_purchase(
+        &mut self,
+        phase: &Phase,
+        buyer_id: &AccountId,
+        amount: u32,
+        cost: Balance,
+        permit: Option<AllowlistPermit>,
+        signature: Option<String>,
+    ) {
+        assert!(amount > 0, "ERR_ZERO_AMOUNT");
+        if self.sold.get(buyer_id).is_none() {
+            self.sold.insert(buyer_id, &0);
+        }
+        let wallet_key = (phase.name.clone(), buyer_id.clone());
+        let phase_wallet_sold = self.phase_wallet_sold.get(&wallet_key).unwrap_or_else(|| {
+            self.phase_wallet_sold.insert(&wallet_key, &0);
+            0
+        });
+        // Configured phases count wallets per phase. The legacy private and open sale
+        // keep counting every purchase, as before phases existed.
+        let already_sold = if self.phases.is_empty() {
+            self.sold.get(buyer_id).unwrap()
+        } else {
+            phase_wallet_sold
+        };
+
+        if phase.allowlist {
+            let permit = permit.expect("ERR_NOT_ALLOWLISTED");
+            let signature = signature.expect("ERR_WRONG_SIG:MISS");
+            self.assert_valid_permit(phase, buyer_id, &permit, signature, amount, cost);
+            assert!(already_sold + amount <= permit.permitted_amount, "ERR_TOO_MUCH");
+        }
+        let (wallet_cap, transaction_cap) = self.internal_phase_caps(phase);
+        if let Some(wallet_cap) = wallet_cap {
+            assert!(already_sold + amount <= wallet_cap, "ERR_TOO_MUCH");
+        }
+        if let Some(transaction_cap) = transaction_cap {
+            assert!(amount <= transaction_cap, "ERR_TOO_MUCH_PER_TX");
+        }
+        if let (true, Some(public_allocation)) = (phase.public_limits, self.public_allocation) {
+            assert!(self.public_sold + amount <= public_allocation, "ERR_PUBLIC_SOLD_OUT");
+        }
+        if let Some(supply_cap) = phase.supply_cap {
+            let phase_sold = self.phase_sold.get(&phase.name).unwrap_or_default();
+            assert!(phase_sold + amount <= supply_cap, "ERR_PHASE_SOLD_OUT");
+        }
+        self.internal_record_sale(buyer_id, &phase.name, !phase.allowlist, amount);
+    }
+
+    pub(crate) fn internal_record_sale(
+        &mut self,
+        buyer_id: &AccountId,
+        phase_name: &str,
+        public: bool,
+        amount: u32,
+    ) {
+        let already_sold = self.sold.get(buyer_id).unwrap();
+        self.sold.insert(buyer_id, &(already_sold + amount));
+        let wallet_key = (phase_name.to_string(), buyer_id.clone());
+        let phase_sold = self.phase_sold.get(&wallet_key.0).unwrap_or_default();
+        self.phase_sold.insert(&wallet_key.0, &(phase_sold + amount));
+        let wallet_sold = self.phase_wallet_sold.get(&wallet_key).unwrap();
+        self.phase_wallet_sold.insert(&wallet_key, &(wallet_sold + amount));
+        if public {
+            self.public_sold += amount;
+        }
+        self.total_sold += amount;
+    }
+
+    /// Gives back `amount` passes reserved by `internal_check_purchase` that weren't minted.
+    pub(crate) fn internal_release_sale(
+        &mut self,
+        buyer_id: &AccountId,
+        phase_name: &str,
+        public: bool,
+        amount: u32,
+    ) {
+        if amount == 0 {
+            return;
+        }
+        let already_sold = self.sold.get(buyer_id).unwrap();
+        self.sold.insert(buyer_id, &(already_sold - amount));
+        let wallet_key = (phase_name.to_string(), buyer_id.clone());
+        let phase_sold = self.phase_sold.get(&wallet_key.0).unwrap();
+        self.phase_sold.insert(&wallet_key.0, &(phase_sold - amount));
+        let wallet_sold = self.phase_wallet_sold.get(&wallet_key).unwrap();
+        self.phase_wallet_sold.insert(&wallet_key, &(wallet_sold - amount));
+        if public {
+            self.public_sold -= amount;
+        }
+        self.total_sold -= amount;
+    }
+}
 
 #[near_bindgen]
 impl Contract {
     #[init]
     pub fn new(nft_account_id: AccountId) -> Self {
         assert!(!env::state_exists(), "Already initialized");
-        Self {
+        Self::from(ContractV1 {
             owner_id: env::predecessor_account_id(),
             treasury_id: env::predecessor_account_id(),
             operators: UnorderedSet::new(StorageKey::Operator),
             nft_account_id,
-            price_in_yocto: 175 * 10_u128.pow(23),
-            signer_pk: None,
             sold: UnorderedMap::new(StorageKey::Sold),
+            signer_pk: None,
             private_sale_timestamp: 0,
             open_sale_timestamp: 0,
-        }
+            price_in_yocto: 175 * 10_u128.pow(23),
+        })
     }
 
     pub fn status(self, account_id: AccountId) -> Status {
@@ -109,36 +295,54 @@ impl Contract {
 
     pub fn config(self) -> Config {
         assert!(env::state_exists(), "State is not initialized");
+        let now = now_sec();
+        let phases = self.internal_phases();
+        let active_phase = phases.iter().find(|phase| phase.is_active(now)).cloned();
+        let upcoming_phases: Vec<Phase> =
+            phases.iter().filter(|phase| phase.start > now).cloned().collect();
+        let tier_remaining = self.internal_current_tier().map(|(_, remaining)| remaining);
         Config {
             signer_pk: self.signer_pk,
+            signer_key_type: self.signer_key_type,
             owner_id: self.owner_id,
             nft_account_id: self.nft_account_id,
             price_in_yocto: self.price_in_yocto.into(),
             private_sale_timestamp: self.private_sale_timestamp / 1_000_000_000_u64,
             open_sale_timestamp: self.open_sale_timestamp / 1_000_000_000_u64,
             curret_timestamp: env::block_timestamp() / 1_000_000_000_u64,
-            stage: match env::block_timestamp() {
-                ts if self.private_sale_timestamp == 0 || ts < self.private_sale_timestamp => {
-                    "SOON".to_string()
-                }
-                ts if ts < self.open_sale_timestamp => "PRIVATE".to_string(),
-                _ => "OPEN".to_string(),
+            stage: match &active_phase {
+                Some(phase) => phase.name.clone(),
+                None if phases.is_empty() || !upcoming_phases.is_empty() => "SOON".to_string(),
+                None => "CLOSED".to_string(),
             },
             motivation: "The zero city is coming. <3 Human Guild!".to_string(),
+            active_phase,
+            upcoming_phases,
+            public_wallet_cap: self.public_wallet_cap,
+            transaction_cap: self.transaction_cap,
+            public_allocation: self.public_allocation,
+            public_sold: self.public_sold,
+            tier_remaining,
+            price_tiers: self.price_tiers,
+            total_sold: self.total_sold,
         }
     }
 
-    pub fn sudo_config(
-        &mut self,
-        nft_account_id: Option<AccountId>,
-        treasury_id: Option<AccountId>,
-        price_in_yocto: Option<U128>,
-        private_sale_timestamp: Option<u32>,
-        open_sale_timestamp: Option<u32>,
-        signer_pk: Option<String>,
-    ) {
+    /// Public limits of 0 remove the limit.
+    pub fn sudo_config(&mut self, config: ConfigUpdate) {
         self.assert_owner_or_operator();
         assert!(env::state_exists(), "State is not initialized");
+        let ConfigUpdate {
+            nft_account_id,
+            treasury_id,
+            price_in_yocto,
+            private_sale_timestamp,
+            open_sale_timestamp,
+            signer_pk,
+            public_wallet_cap,
+            transaction_cap,
+            public_allocation,
+        } = config;
         update_if_exists!(self, nft_account_id);
         update_if_exists!(self, treasury_id);
         update_if_exists!(self, price_in_yocto, price_in_yocto.0);
@@ -152,40 +356,59 @@ impl Contract {
             open_sale_timestamp,
             open_sale_timestamp as u64 * 1_000_000_000_u64
         );
+        if let Some(signer_pk) = &signer_pk {
+            self.signer_key_type = KeyType::of(signer_pk);
+        }
         update_if_exists!(self, signer_pk, Some(signer_pk));
+        update_if_exists!(self, public_wallet_cap, Some(public_wallet_cap).filter(|cap| *cap > 0));
+        update_if_exists!(self, transaction_cap, Some(transaction_cap).filter(|cap| *cap > 0));
+        update_if_exists!(self, public_allocation, Some(public_allocation).filter(|cap| *cap > 0));
     }
 
     #[private]
     pub fn callback_on_nft_mints(
         &mut self,
-        price: Balance,
+        purchase: Purchase,
         attached_deposit: Balance,
-        desired_amount: u32,
     ) -> Vec<Token> {
+        let Purchase { buyer_id, phase_name, public, amount: desired_amount, cost } = purchase;
         assert_eq!(env::predecessor_account_id(), env::current_account_id(), "ERR_WRONG_CALLBACK");
         assert_eq!(env::promise_results_count(), 1);
         match env::promise_result(0) {
             PromiseResult::NotReady => unreachable!(),
             PromiseResult::Failed => {
-                Promise::new(env::signer_account_id())
-                    .transfer(attached_deposit + MINT_COST * desired_amount as u128);
+                self.internal_release_sale(&buyer_id, &phase_name, public, desired_amount);
+                Promise::new(buyer_id).transfer(attached_deposit + mint_deposit(desired_amount));
                 vec![]
             }
             PromiseResult::Successful(val) => {
-                let tokens: Vec<Token> =
+                let MintedTokens { tokens, storage_cost } =
                     near_sdk::serde_json::from_slice(&val).expect("ERR_WRONG_VAL_RECEIVED");
                 let actual_amount = tokens.len() as u32;
-                let refund: Balance = attached_deposit
-                    + (desired_amount - actual_amount) as u128 * (MINT_COST + price)
-                    - (actual_amount) as u128 * price;
-                let already_sold = self.sold.get(&env::signer_account_id()).unwrap();
-                self.sold.insert(&env::signer_account_id(), &(already_sold + actual_amount));
+                let cost = cost.0 * actual_amount as u128 / desired_amount as u128;
+                // nft refunded whatever part of the mint deposit it didn't use for storage
+                let refund: Balance =
+                    attached_deposit + mint_deposit(desired_amount) - storage_cost.0 - cost;
+                self.internal_release_sale(
+                    &buyer_id,
+                    &phase_name,
+                    public,
+                    desired_amount - actual_amount,
+                );
 
-                Promise::new(self.owner_id.clone())
-                    .transfer(self.price_in_yocto * actual_amount as u128);
+                let held = match self.internal_phase(&phase_name) {
+                    Some(phase) => self.internal_record_auction_purchase(
+                        &phase,
+                        &buyer_id,
+                        cost / actual_amount as u128,
+                        actual_amount,
+                    ),
+                    None => 0,
+                };
+                self.internal_pay_proceeds(None, cost - held);
 
                 if refund > 0 {
-                    Promise::new(env::signer_account_id()).transfer(refund);
+                    Promise::new(buyer_id).transfer(refund);
                 }
                 tokens
             }
@@ -196,68 +419,47 @@ impl Contract {
     pub fn sacrifice(
         &mut self,
         amount: u32,
-        permitted_amount: Option<u32>,
+        permit: Option<AllowlistPermit>,
         signature: Option<String>,
     ) -> Promise {
-        assert_ne!(self.open_sale_timestamp, 0, "ERR_NOT_STARTED");
-        assert_ne!(self.private_sale_timestamp, 0, "ERR_NOT_STARTED");
+        let phase = self.internal_current_phase().expect("ERR_NOT_STARTED");
         assert!(
             env::prepaid_gas()
-                >= GAS_FOR_NFT_MINT_CALL * amount.into()
+                >= GAS_FOR_NFT_MINTS
+                    + GAS_FOR_NFT_MINT_CALL * amount.into()
                     + GAS_FOR_RESOLVE_TRANSFER
                     + GAS_FOR_SACRIFICE,
             "ERR_NOT_ENOUGH_GAS"
         );
 
         let receiver_id = env::predecessor_account_id();
-        let mut attached_deposit = env::attached_deposit() - MINT_COST * amount as u128;
-        let already_sold = match self.sold.get(&receiver_id) {
-            None => {
-                let storage_usage = env::storage_usage();
-                self.sold.insert(&receiver_id, &0);
-                attached_deposit = attached_deposit
-                    - (env::storage_usage() - storage_usage) as u128 * env::storage_byte_cost();
-                0
-            }
-            Some(value) => value,
-        };
-
-        if self.open_sale_timestamp < env::block_timestamp() {
-            // Skip check, already public
-            assert!(already_sold + amount <= 2, "ERR_TOO_MUCH");
-        } else if self.private_sale_timestamp < env::block_timestamp()
-            && permitted_amount.is_some()
-            && signature.is_some()
-        {
-            // Private, check signature and permitted_amount
-            let permitted_amount = permitted_amount.unwrap();
-            assert!(self.signer_pk.is_some(), "ERR_NOT_VALID_SIGNER");
-            assert!(
-                self.verify_signature(
-                    self.signer_pk.clone().unwrap(),
-                    signature.expect("ERR_WRONG_SIG:MISS"),
-                    format!("{}:{}", env::predecessor_account_id(), permitted_amount)
-                ),
-                "ERR_WRONG_SIG:PAYLOAD"
-            );
-            assert!(already_sold + amount <= permitted_amount, "ERR_TOO_MUCH");
-        } else {
-            env::panic_str("ERR_NOT_STARTED");
+        if let Some(permit) = &permit {
+            permit.assert_paid_in(None);
         }
-
-        assert!(attached_deposit >= amount as u128 * self.price_in_yocto, "ERR_NOT_ENOUGH");
+        let cost = self.internal_phase_cost(&phase, amount);
+        let storage_usage = env::storage_usage();
+        self.internal_check_purchase(&phase, &receiver_id, amount, cost, permit, signature);
+        let attached_deposit = env::attached_deposit()
+            - mint_deposit(amount)
+            - (env::storage_usage() - storage_usage) as u128 * env::storage_byte_cost();
+        assert!(attached_deposit >= cost, "ERR_NOT_ENOUGH");
 
         ext_nft::nft_mints(
-            env::predecessor_account_id().to_string(),
+            receiver_id.to_string(),
             amount,
             self.nft_account_id.clone(),
-            MINT_COST * amount as u128,
+            mint_deposit(amount),
             env::prepaid_gas() - GAS_FOR_SACRIFICE - GAS_FOR_RESOLVE_TRANSFER,
         )
         .then(ext_halloffame::callback_on_nft_mints(
-            self.price_in_yocto,
+            Purchase {
+                buyer_id: receiver_id,
+                phase_name: phase.name,
+                public: !phase.allowlist,
+                amount,
+                cost: cost.into(),
+            },
             attached_deposit,
-            amount,
             env::current_account_id(),
             0,
             GAS_FOR_RESOLVE_TRANSFER,
@@ -0,0 +1,63 @@
+use crate::*;
+
+/// Layout of `Contract` before phases and the rest of the sale extensions were added.
+#[derive(BorshDeserialize)]
+pub(crate) struct ContractV1 {
+    pub owner_id: AccountId,
+    pub treasury_id: AccountId,
+    pub operators: UnorderedSet<AccountId>,
+    pub nft_account_id: AccountId,
+    pub sold: UnorderedMap<AccountId, u32>,
+    pub signer_pk: Option<String>,
+    pub private_sale_timestamp: u64,
+    pub open_sale_timestamp: u64,
+    pub price_in_yocto: Balance,
+}
+
+impl From<ContractV1> for Contract {
+    /// Keeps the sale of `old`, counting its passes sold so far in `total_sold`.
+    fn from(old: ContractV1) -> Self {
+        let total_sold = old.sold.values().sum();
+        Self {
+            owner_id: old.owner_id,
+            treasury_id: old.treasury_id,
+            operators: old.operators,
+            nft_account_id: old.nft_account_id,
+            sold: old.sold,
+            signer_pk: old.signer_pk,
+            private_sale_timestamp: old.private_sale_timestamp,
+            open_sale_timestamp: old.open_sale_timestamp,
+            price_in_yocto: old.price_in_yocto,
+            phases: vec![],
+            phase_sold: LookupMap::new(StorageKey::PhaseSold),
+            phase_wallet_sold: LookupMap::new(StorageKey::PhaseWalletSold),
+            public_wallet_cap: Some(2),
+            transaction_cap: None,
+            public_allocation: None,
+            public_sold: 0,
+            auction_paid: LookupMap::new(StorageKey::AuctionPaid),
+            clearing_prices: LookupMap::new(StorageKey::ClearingPrices),
+            auction_floors: LookupMap::new(StorageKey::AuctionFloors),
+            price_tiers: vec![],
+            total_sold,
+            ft_prices: UnorderedMap::new(StorageKey::FtPrices),
+            ft_mint_storage: LookupMap::new(StorageKey::FtMintStorage),
+            revenue_split: vec![],
+            proceeds: LookupMap::new(StorageKey::Proceeds),
+            signer_key_type: KeyType::Secp256k1,
+            signer_keys: UnorderedMap::new(StorageKey::SignerKeys),
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Upgrade the state of a sale deployed before phases. Sales of the legacy private
+    /// and open stages keep counting per wallet in `sold`.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old: ContractV1 = env::state_read().expect("ERR_NO_STATE");
+        old.into()
+    }
+}
@@ -0,0 +1,146 @@
+use crate::*;
+use near_sdk::near_bindgen;
+
+/// A sale window. Timestamps are unix seconds, `end` of 0 means the phase never ends.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct Phase {
+    pub name: String,
+    pub start: u64,
+    pub end: u64,
+    /// Price per pass, `price_tiers` or `price_in_yocto` if not set.
+    pub price: Option<U128>,
+    /// Passes a single wallet can buy during the phase.
+    pub wallet_cap: Option<u32>,
+    /// Passes a single `sacrifice` can buy.
+    pub transaction_cap: Option<u32>,
+    /// Buyers need a permit signed by a signer key.
+    pub allowlist: bool,
+    /// Passes sold during the phase across all wallets.
+    pub supply_cap: Option<u32>,
+    /// Prices the phase as a Dutch auction instead of `price`.
+    pub dutch_auction: Option<DutchAuction>,
+    /// Opt in to `public_wallet_cap`, `transaction_cap` and `public_allocation` where the
+    /// phase has no cap of its own. Set for the legacy open sale.
+    pub public_limits: bool,
+}
+
+impl Phase {
+    pub(crate) fn is_active(&self, now: u64) -> bool {
+        self.start <= now && (self.end == 0 || now < self.end)
+    }
+}
+
+pub(crate) fn now_sec() -> u64 {
+    env::block_timestamp() / 1_000_000_000_u64
+}
+
+impl Contract {
+    /// Configured phases or, if there are none, the private and open sale
+    /// derived from `private_sale_timestamp` and `open_sale_timestamp`.
+    pub(crate) fn internal_phases(&self) -> Vec<Phase> {
+        if !self.phases.is_empty() {
+            return self.phases.clone();
+        }
+        if self.private_sale_timestamp == 0 || self.open_sale_timestamp == 0 {
+            return vec![];
+        }
+        let private_start = self.private_sale_timestamp / 1_000_000_000_u64;
+        let open_start = self.open_sale_timestamp / 1_000_000_000_u64;
+        vec![
+            Phase {
+                name: "PRIVATE".to_string(),
+                start: private_start,
+                end: open_start,
+                price: None,
+                wallet_cap: None,
+                transaction_cap: None,
+                allowlist: true,
+                supply_cap: None,
+                dutch_auction: None,
+                public_limits: false,
+            },
+            Phase {
+                name: "OPEN".to_string(),
+                start: open_start,
+                end: 0,
+                price: None,
+                wallet_cap: None,
+                transaction_cap: None,
+                allowlist: false,
+                supply_cap: None,
+                dutch_auction: None,
+                public_limits: true,
+            },
+        ]
+    }
+
+    pub(crate) fn internal_current_phase(&self) -> Option<Phase> {
+        let now = now_sec();
+        self.internal_phases().into_iter().find(|phase| phase.is_active(now))
+    }
+
+    /// Wallet and transaction caps of `phase`. Phases with `public_limits` and without
+    /// their own caps use `public_wallet_cap` and `transaction_cap`.
+    pub(crate) fn internal_phase_caps(&self, phase: &Phase) -> (Option<u32>, Option<u32>) {
+        if !phase.public_limits {
+            return (phase.wallet_cap, phase.transaction_cap);
+        }
+        (
+            phase.wallet_cap.or(self.public_wallet_cap),
+            phase.transaction_cap.or(self.transaction_cap),
+        )
+    }
+
+    /// Cost of `amount` passes bought now during `phase`.
+    pub(crate) fn internal_phase_cost(&self, phase: &Phase, amount: u32) -> Balance {
+        let unit_price = match (&phase.dutch_auction, phase.price) {
+            (Some(auction), _) => auction.price_at(now_sec().saturating_sub(phase.start)),
+            (None, Some(price)) => price.0,
+            (None, None) if !self.price_tiers.is_empty() => {
+                return self.internal_tiered_cost(amount)
+            }
+            (None, None) => self.price_in_yocto,
+        };
+        unit_price * amount as u128
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Replace the sale schedule. Phases must be ordered and must not overlap. An auction
+    /// with rebates can't change its floor once sold from, but can be removed.
+    /// An empty list falls back to the private and open sale timestamps.
+    pub fn set_phases(&mut self, phases: Vec<Phase>) {
+        self.assert_owner_or_operator();
+        for (i, phase) in phases.iter().enumerate() {
+            assert!(!phase.name.is_empty() && !phase.name.contains(':'), "ERR_WRONG_PHASE:NAME");
+            assert!(phase.end == 0 || phase.start < phase.end, "ERR_WRONG_PHASE:TIME");
+            assert!(phases[..i].iter().all(|p| p.name != phase.name), "ERR_WRONG_PHASE:NAME");
+            self.assert_auction_unchanged(phase);
+            if let Some(auction) = &phase.dutch_auction {
+                auction.assert_valid();
+                assert!(
+                    !auction.rebates || phase.end != 0 || phase.supply_cap.is_some(),
+                    "ERR_WRONG_AUCTION:REBATES"
+                );
+            }
+            if i > 0 {
+                let previous = &phases[i - 1];
+                assert!(previous.end != 0 && previous.end <= phase.start, "ERR_WRONG_PHASE:ORDER");
+            }
+        }
+        self.phases = phases;
+    }
+
+    pub fn phases(&self) -> Vec<Phase> {
+        self.internal_phases()
+    }
+
+    pub fn phase_sold(&self, name: String, account_id: Option<AccountId>) -> u32 {
+        match account_id {
+            Some(account_id) => self.phase_wallet_sold.get(&(name, account_id)),
+            None => self.phase_sold.get(&name),
+        }
+        .unwrap_or_default()
+    }
+}
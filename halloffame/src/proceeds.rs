@@ -0,0 +1,121 @@
+use crate::events::emit_event;
+use crate::*;
+use near_sdk::serde_json::json;
+use near_sdk::{assert_one_yocto, near_bindgen};
+
+/// Proceeds of a beneficiary in NEAR or, with `token_id`, in a fungible token.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Proceeds {
+    pub token_id: Option<AccountId>,
+    pub pending: U128,
+    pub withdrawn: U128,
+}
+
+impl Contract {
+    /// Credits the revenue split with `amount`, beneficiaries `withdraw` it later.
+    pub(crate) fn internal_pay_proceeds(&mut self, token_id: Option<AccountId>, amount: Balance) {
+        if amount == 0 {
+            return;
+        }
+        for (account_id, share) in self.internal_distribute(token_id.as_ref(), amount) {
+            if share > 0 {
+                self.internal_update_proceeds(&account_id, &token_id, |proceeds| {
+                    proceeds.pending.0 += share
+                });
+            }
+        }
+    }
+
+    fn internal_update_proceeds<F: FnOnce(&mut Proceeds)>(
+        &mut self,
+        account_id: &AccountId,
+        token_id: &Option<AccountId>,
+        update: F,
+    ) {
+        let mut all_proceeds = self.proceeds.get(account_id).unwrap_or_default();
+        let index = match all_proceeds.iter().position(|p| &p.token_id == token_id) {
+            Some(index) => index,
+            None => {
+                all_proceeds.push(Proceeds {
+                    token_id: token_id.clone(),
+                    pending: U128(0),
+                    withdrawn: U128(0),
+                });
+                all_proceeds.len() - 1
+            }
+        };
+        update(&mut all_proceeds[index]);
+        self.proceeds.insert(account_id, &all_proceeds);
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Withdraw pending proceeds of the caller in NEAR or, with `token_id`, in that token.
+    #[payable]
+    pub fn withdraw(&mut self, token_id: Option<AccountId>) -> Promise {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        let amount = self
+            .proceeds
+            .get(&account_id)
+            .and_then(|all| all.into_iter().find(|p| p.token_id == token_id))
+            .map_or(0, |proceeds| proceeds.pending.0);
+        assert!(amount > 0, "ERR_NOTHING_TO_WITHDRAW");
+        self.internal_update_proceeds(&account_id, &token_id, |proceeds| {
+            proceeds.pending.0 = 0;
+            proceeds.withdrawn.0 += amount;
+        });
+        emit_event(
+            "withdraw",
+            json!({ "account_id": account_id, "token_id": token_id, "amount": U128(amount) }),
+        );
+
+        let transfer = match &token_id {
+            Some(token_id) => ext_ft::ft_transfer(
+                account_id.clone(),
+                amount.into(),
+                None,
+                token_id.clone(),
+                1,
+                GAS_FOR_FT_TRANSFER,
+            ),
+            None => Promise::new(account_id.clone()).transfer(amount),
+        };
+        transfer.then(ext_halloffame::callback_on_withdraw(
+            account_id,
+            token_id,
+            amount.into(),
+            env::current_account_id(),
+            0,
+            GAS_FOR_RESOLVE_TRANSFER,
+        ))
+    }
+
+    /// Restores the pending proceeds if the transfer failed.
+    #[private]
+    pub fn callback_on_withdraw(
+        &mut self,
+        account_id: AccountId,
+        token_id: Option<AccountId>,
+        amount: U128,
+    ) -> bool {
+        assert_eq!(env::promise_results_count(), 1);
+        match env::promise_result(0) {
+            PromiseResult::NotReady => unreachable!(),
+            PromiseResult::Successful(_) => true,
+            PromiseResult::Failed => {
+                self.internal_update_proceeds(&account_id, &token_id, |proceeds| {
+                    proceeds.pending.0 += amount.0;
+                    proceeds.withdrawn.0 -= amount.0;
+                });
+                false
+            }
+        }
+    }
+
+    /// Pending and withdrawn proceeds of `account_id` per token.
+    pub fn proceeds_of(&self, account_id: AccountId) -> Vec<Proceeds> {
+        self.proceeds.get(&account_id).unwrap_or_default()
+    }
+}
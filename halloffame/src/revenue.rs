@@ -0,0 +1,81 @@
+use crate::events::emit_event;
+use crate::*;
+use near_sdk::serde_json::json;
+use near_sdk::{assert_one_yocto, near_bindgen};
+
+pub const FULL_BPS: u16 = 10_000;
+
+/// Share of the proceeds paid to `account_id`, in basis points.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Beneficiary {
+    pub account_id: AccountId,
+    pub bps: u16,
+}
+
+impl Contract {
+    /// Configured split or, if there is none, everything to the current `treasury_id`.
+    fn internal_revenue_split(&self) -> Vec<Beneficiary> {
+        if self.revenue_split.is_empty() {
+            return vec![Beneficiary { account_id: self.treasury_id.clone(), bps: FULL_BPS }];
+        }
+        self.revenue_split.clone()
+    }
+
+    /// Splits `amount` by the revenue split, the rounding remainder goes to the
+    /// first beneficiary. Logs a `distribution` event, `token_id` is `None` for NEAR.
+    pub(crate) fn internal_distribute(
+        &self,
+        token_id: Option<&AccountId>,
+        amount: Balance,
+    ) -> Vec<(AccountId, Balance)> {
+        let split = self.internal_revenue_split();
+        let mut payouts: Vec<(AccountId, Balance)> = split
+            .into_iter()
+            .map(|b| (b.account_id, amount * b.bps as u128 / FULL_BPS as u128))
+            .collect();
+        let paid: Balance = payouts.iter().map(|(_, share)| share).sum();
+        payouts[0].1 += amount - paid;
+        emit_event(
+            "distribution",
+            json!({
+                "token_id": token_id,
+                "amount": U128(amount),
+                "payouts": payouts
+                    .iter()
+                    .map(|(account_id, share)| json!({
+                        "account_id": account_id,
+                        "amount": U128(*share),
+                    }))
+                    .collect::<Vec<_>>(),
+            }),
+        );
+        payouts
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Replace the revenue split, shares must add up to 10000 bps. An empty split
+    /// pays everything to `treasury_id`.
+    #[payable]
+    pub fn set_revenue_split(&mut self, split: Vec<Beneficiary>) {
+        assert_one_yocto();
+        self.assert_owner();
+        if !split.is_empty() {
+            let total: u32 = split.iter().map(|b| b.bps as u32).sum();
+            assert_eq!(total, FULL_BPS as u32, "ERR_WRONG_SPLIT:TOTAL");
+        }
+        for (i, beneficiary) in split.iter().enumerate() {
+            assert!(beneficiary.bps > 0, "ERR_WRONG_SPLIT:BPS");
+            assert!(
+                split[..i].iter().all(|b| b.account_id != beneficiary.account_id),
+                "ERR_WRONG_SPLIT:DUPLICATE"
+            );
+        }
+        self.revenue_split = split;
+    }
+
+    pub fn revenue_split(&self) -> Vec<Beneficiary> {
+        self.internal_revenue_split()
+    }
+}
@@ -0,0 +1,108 @@
+use crate::events::emit_event;
+use crate::phases::now_sec;
+use crate::*;
+use near_sdk::serde_json::json;
+use near_sdk::{assert_one_yocto, near_bindgen};
+
+/// Signer keys besides `signer_pk`, checked on every allowlist purchase.
+pub const MAX_SIGNER_KEYS: u64 = 10;
+
+/// Key allowed to sign allowlist permits. Timestamps are unix seconds, 0 means unbounded.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct SignerKey {
+    pub public_key: String,
+    pub key_type: KeyType,
+    pub label: String,
+    pub valid_from: u64,
+    pub valid_until: u64,
+}
+
+impl SignerKey {
+    pub(crate) fn is_valid(&self, now: u64) -> bool {
+        self.valid_from <= now && (self.valid_until == 0 || now < self.valid_until)
+    }
+}
+
+impl Contract {
+    /// `signer_pk` followed by the added keys.
+    pub(crate) fn internal_signer_keys(&self) -> Vec<SignerKey> {
+        let default_key = self.signer_pk.clone().map(|public_key| SignerKey {
+            public_key,
+            key_type: self.signer_key_type,
+            label: "default".to_string(),
+            valid_from: 0,
+            valid_until: 0,
+        });
+        default_key.into_iter().chain(self.signer_keys.values()).collect()
+    }
+
+    /// Whether any currently valid key signed `payload`.
+    pub(crate) fn internal_verify_signers(&self, signature: String, payload: String) -> bool {
+        let now = now_sec();
+        let keys: Vec<SignerKey> =
+            self.internal_signer_keys().into_iter().filter(|key| key.is_valid(now)).collect();
+        assert!(!keys.is_empty(), "ERR_NOT_VALID_SIGNER");
+        keys.into_iter().any(|key| {
+            self.verify_signature(key.key_type, key.public_key, signature.clone(), payload.clone())
+        })
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Add a permit signer key, secp256k1 hex or NEAR `ed25519:` format, valid between
+    /// `valid_from` and `valid_until`. Only owner.
+    #[payable]
+    pub fn add_signer_key(
+        &mut self,
+        public_key: String,
+        label: String,
+        valid_from: Option<u64>,
+        valid_until: Option<u64>,
+    ) {
+        assert_one_yocto();
+        self.assert_owner();
+        let key = SignerKey {
+            key_type: KeyType::of(&public_key),
+            public_key,
+            label,
+            valid_from: valid_from.unwrap_or_default(),
+            valid_until: valid_until.unwrap_or_default(),
+        };
+        assert!(key.valid_until == 0 || key.valid_from < key.valid_until, "ERR_WRONG_KEY:WINDOW");
+        assert!(self.signer_keys.len() < MAX_SIGNER_KEYS, "ERR_TOO_MANY_KEYS");
+        assert!(
+            self.signer_pk.as_ref() != Some(&key.public_key)
+                && self.signer_keys.get(&key.public_key).is_none(),
+            "ERR_KEY_EXISTS"
+        );
+        self.signer_keys.insert(&key.public_key, &key);
+        emit_event(
+            "signer_key_added",
+            json!({
+                "public_key": key.public_key,
+                "label": key.label,
+                "valid_from": key.valid_from,
+                "valid_until": key.valid_until,
+            }),
+        );
+    }
+
+    /// Revoke a signer key, `signer_pk` included. Only owner.
+    #[payable]
+    pub fn revoke_signer_key(&mut self, public_key: String) {
+        assert_one_yocto();
+        self.assert_owner();
+        if self.signer_pk.as_ref() == Some(&public_key) {
+            self.signer_pk = None;
+        } else {
+            self.signer_keys.remove(&public_key).expect("ERR_NO_KEY");
+        }
+        emit_event("signer_key_revoked", json!({ "public_key": public_key }));
+    }
+
+    /// All signer keys, including ones outside their validity window.
+    pub fn signer_keys(&self) -> Vec<SignerKey> {
+        self.internal_signer_keys()
+    }
+}
@@ -0,0 +1,52 @@
+use crate::*;
+use near_sdk::near_bindgen;
+
+/// `amount` passes at `price` each, after the passes of the previous tiers are sold.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct PriceTier {
+    pub amount: u32,
+    pub price: U128,
+}
+
+impl Contract {
+    /// Cost of the next `amount` passes, each unit priced by the tier it falls in.
+    pub(crate) fn internal_tiered_cost(&self, amount: u32) -> Balance {
+        let mut skip = self.total_sold;
+        let mut left = amount;
+        let mut cost: Balance = 0;
+        for tier in self.price_tiers.iter() {
+            let available = tier.amount.saturating_sub(skip);
+            skip = skip.saturating_sub(tier.amount);
+            let units = available.min(left);
+            cost += units as u128 * tier.price.0;
+            left -= units;
+            if left == 0 {
+                return cost;
+            }
+        }
+        env::panic_str("ERR_TIERS_SOLD_OUT")
+    }
+
+    /// Index of the tier the next pass is sold in and passes left in it.
+    pub(crate) fn internal_current_tier(&self) -> Option<(usize, u32)> {
+        let mut sold = self.total_sold;
+        for (index, tier) in self.price_tiers.iter().enumerate() {
+            if sold < tier.amount {
+                return Some((index, tier.amount - sold));
+            }
+            sold -= tier.amount;
+        }
+        None
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Replace `price_in_yocto` with prices by passes sold. Applies to phases without
+    /// their own price, an empty list restores `price_in_yocto`.
+    pub fn set_price_tiers(&mut self, price_tiers: Vec<PriceTier>) {
+        self.assert_owner_or_operator();
+        assert!(price_tiers.iter().all(|tier| tier.amount > 0), "ERR_WRONG_TIER:AMOUNT");
+        self.price_tiers = price_tiers;
+    }
+}
@@ -5,7 +5,22 @@ pub const ONE: Balance = 1 * 10u128.pow(18);
 
 pub const TGAS: Gas = Gas(10u64.pow(12));
 pub const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(15_000_000_000_000);
+pub const GAS_FOR_FT_TRANSFER: Gas = Gas(10_000_000_000_000);
 pub const GAS_FOR_FT_TRANSFER_CALL: Gas = Gas(30_000_000_000_000);
 pub const GAS_FOR_NFT_TRANSFER_CALL: Gas = Gas(25_000_000_000_000);
-pub const GAS_FOR_NFT_MINT_CALL: Gas = Gas(10_000_000_000_000);
+/// Fixed part of an `nft_mints` call, plus `GAS_FOR_NFT_MINT_CALL` per token.
+pub const GAS_FOR_NFT_MINTS: Gas = Gas(10_000_000_000_000);
+pub const GAS_FOR_NFT_MINT_CALL: Gas = Gas(5_000_000_000_000);
 pub const GAS_FOR_SACRIFICE: Gas = Gas(45_000_000_000_000);
+
+/// Storage deposit attached to `nft_mints` per pass. A view of the nft `mint_storage_cost`
+/// can't be read within the call, so this covers its bound for any pass id and receiver.
+pub const MINT_COST: Balance = 10_u128.pow(20) * 80;
+/// Storage deposit attached to `nft_mints` for the receiver's enumeration set and holder
+/// entry, needed when it holds no pass yet.
+pub const MINT_RECEIVER_COST: Balance = 10_u128.pow(20) * 50;
+
+/// Deposit attached to `nft_mints` for `amount` passes, the unused part is refunded.
+pub fn mint_deposit(amount: u32) -> Balance {
+    MINT_COST * amount as u128 + MINT_RECEIVER_COST
+}
@@ -0,0 +1,185 @@
+use crate::*;
+use near_contract_standards::non_fungible_token::{refund_approved_account_ids, refund_deposit};
+use near_sdk::{json_types::U64, near_bindgen};
+use std::collections::HashMap;
+
+impl Contract {
+    /// Expiry of the approval `account_id` currently holds on `token_id`, if any.
+    /// Expiries are keyed by approval id, so approvals restored after a reverted
+    /// `nft_transfer_call` keep theirs and a fresh approval never inherits a stale one.
+    pub(crate) fn internal_approval_expires_at(
+        &self,
+        token_id: &TokenId,
+        account_id: &AccountId,
+    ) -> Option<u64> {
+        let approval_id = self
+            .tokens
+            .approvals_by_id
+            .as_ref()
+            .and_then(|by_id| by_id.get(token_id))
+            .and_then(|approvals| approvals.get(account_id).copied())?;
+        self.approval_expires_at
+            .get(token_id)
+            .and_then(|expires_at| expires_at.get(&approval_id).copied())
+    }
+
+    pub(crate) fn internal_approval_expired(
+        &self,
+        token_id: &TokenId,
+        account_id: &AccountId,
+    ) -> bool {
+        self.internal_approval_expires_at(token_id, account_id)
+            .is_some_and(|expires_at| expires_at <= env::block_timestamp())
+    }
+
+    /// Drops expiries of approvals that no longer exist on `token_id`.
+    pub(crate) fn internal_prune_approval_expiries(&mut self, token_id: &TokenId) {
+        if let Some(mut expires_at) = self.approval_expires_at.get(token_id) {
+            let approvals = self
+                .tokens
+                .approvals_by_id
+                .as_ref()
+                .and_then(|by_id| by_id.get(token_id))
+                .unwrap_or_default();
+            expires_at.retain(|approval_id, _| approvals.values().any(|id| id == approval_id));
+            if expires_at.is_empty() {
+                self.approval_expires_at.remove(token_id);
+            } else {
+                self.approval_expires_at.insert(token_id, &expires_at);
+            }
+        }
+    }
+
+    /// Same as `NonFungibleToken::nft_approve`, but charges the storage of the
+    /// optional expiry together with the approval itself.
+    fn internal_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        expires_at: Option<u64>,
+        msg: Option<String>,
+    ) -> Option<Promise> {
+        require!(env::attached_deposit() >= 1, "Requires attached deposit of at least 1 yoctoNEAR");
+        let owner_id = self.tokens.owner_by_id.get(&token_id).expect("Token not found");
+        require!(env::predecessor_account_id() == owner_id, "Predecessor must be token owner.");
+
+        let initial_storage_usage = env::storage_usage();
+        let approvals_by_id = self
+            .tokens
+            .approvals_by_id
+            .as_mut()
+            .unwrap_or_else(|| env::panic_str("NFT does not support Approval Management"));
+        let next_approval_id_by_id = self.tokens.next_approval_id_by_id.as_mut().unwrap();
+
+        let mut approved_account_ids = approvals_by_id.get(&token_id).unwrap_or_default();
+        let approval_id = next_approval_id_by_id.get(&token_id).unwrap_or(1u64);
+        approved_account_ids.insert(account_id.clone(), approval_id);
+        approvals_by_id.insert(&token_id, &approved_account_ids);
+        next_approval_id_by_id.insert(&token_id, &(approval_id + 1));
+
+        if let Some(expires_at) = expires_at {
+            let mut expiries = self.approval_expires_at.get(&token_id).unwrap_or_default();
+            expiries.insert(approval_id, expires_at);
+            self.approval_expires_at.insert(&token_id, &expiries);
+        }
+        self.internal_prune_approval_expiries(&token_id);
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+
+        msg.map(|msg| {
+            ext_approval_receiver::nft_on_approve(
+                token_id,
+                owner_id,
+                approval_id,
+                msg,
+                account_id,
+                NO_DEPOSIT,
+                env::prepaid_gas() - GAS_FOR_NFT_APPROVE,
+            )
+        })
+    }
+}
+
+#[near_bindgen]
+impl NonFungibleTokenApproval for Contract {
+    #[payable]
+    fn nft_approve(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        msg: Option<String>,
+    ) -> Option<Promise> {
+        self.internal_approve(token_id, account_id, None, msg)
+    }
+
+    #[payable]
+    fn nft_revoke(&mut self, token_id: TokenId, account_id: AccountId) {
+        self.tokens.nft_revoke(token_id.clone(), account_id);
+        self.internal_prune_approval_expiries(&token_id);
+    }
+
+    #[payable]
+    fn nft_revoke_all(&mut self, token_id: TokenId) {
+        self.tokens.nft_revoke_all(token_id.clone());
+        self.internal_prune_approval_expiries(&token_id);
+    }
+
+    fn nft_is_approved(
+        &self,
+        token_id: TokenId,
+        approved_account_id: AccountId,
+        approval_id: Option<u64>,
+    ) -> bool {
+        !self.internal_approval_expired(&token_id, &approved_account_id)
+            && self.tokens.nft_is_approved(token_id, approved_account_id, approval_id)
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Approve `account_id` to transfer `token_id` until `expires_at` (nanoseconds).
+    #[payable]
+    pub fn nft_approve_until(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        expires_at: U64,
+        msg: Option<String>,
+    ) -> Option<Promise> {
+        require!(expires_at.0 > env::block_timestamp(), "ERR_EXPIRES_IN_PAST");
+        self.internal_approve(token_id, account_id, Some(expires_at.0), msg)
+    }
+
+    /// Expiry of an approval in nanoseconds, `None` if it never expires or doesn't exist.
+    pub fn nft_approval_expires_at(&self, token_id: TokenId, account_id: AccountId) -> Option<U64> {
+        self.internal_approval_expires_at(&token_id, &account_id).map(U64)
+    }
+
+    /// Remove expired approvals of `token_id`, refunding their storage to the token owner.
+    /// Can be called by anyone.
+    pub fn nft_revoke_expired(&mut self, token_id: TokenId) -> Vec<AccountId> {
+        let owner_id = self.tokens.owner_by_id.get(&token_id).expect("Token not found");
+        let mut approvals =
+            match self.tokens.approvals_by_id.as_ref().and_then(|a| a.get(&token_id)) {
+                Some(approvals) => approvals,
+                None => return vec![],
+            };
+        let expired: HashMap<AccountId, u64> = approvals
+            .iter()
+            .filter(|(account_id, _)| self.internal_approval_expired(&token_id, account_id))
+            .map(|(account_id, approval_id)| (account_id.clone(), *approval_id))
+            .collect();
+        if expired.is_empty() {
+            return vec![];
+        }
+        approvals.retain(|account_id, _| !expired.contains_key(account_id));
+        let approvals_by_id = self.tokens.approvals_by_id.as_mut().unwrap();
+        if approvals.is_empty() {
+            approvals_by_id.remove(&token_id);
+        } else {
+            approvals_by_id.insert(&token_id, &approvals);
+        }
+        self.internal_prune_approval_expiries(&token_id);
+        refund_approved_account_ids(owner_id, &expired);
+        expired.into_keys().collect()
+    }
+}
@@ -0,0 +1,88 @@
+use crate::*;
+use near_contract_standards::non_fungible_token::refund_deposit;
+use near_sdk::{assert_one_yocto, json_types::U128, near_bindgen, serde_json::json};
+
+impl Contract {
+    pub(crate) fn internal_is_approved_for_all(
+        &self,
+        owner_id: &AccountId,
+        account_id: &AccountId,
+    ) -> bool {
+        self.approvals_for_all
+            .get(owner_id)
+            .is_some_and(|account_ids| account_ids.contains(account_id))
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Approve `account_id` to transfer every token of the predecessor, current and future.
+    /// Requires a deposit covering the storage of the approval.
+    #[payable]
+    pub fn nft_approve_for_all(&mut self, account_id: AccountId) {
+        require!(env::attached_deposit() >= 1, "Requires attached deposit of at least 1 yoctoNEAR");
+        let owner_id = env::predecessor_account_id();
+        require!(owner_id != account_id, "ERR_APPROVE_SELF");
+
+        let initial_storage_usage = env::storage_usage();
+        let mut account_ids = self.approvals_for_all.get(&owner_id).unwrap_or_else(|| {
+            UnorderedSet::new(StorageKey::ApprovalsForAllPerOwner {
+                account_hash: env::sha256(owner_id.as_bytes()),
+            })
+        });
+        account_ids.insert(&account_id);
+        self.approvals_for_all.insert(&owner_id, &account_ids);
+        refund_deposit(env::storage_usage() - initial_storage_usage);
+
+        emit_event("approve_for_all", json!({ "owner_id": owner_id, "account_id": account_id }));
+    }
+
+    /// Revoke an approval given by `nft_approve_for_all`, refunding its storage.
+    #[payable]
+    pub fn nft_revoke_for_all(&mut self, account_id: AccountId) {
+        assert_one_yocto();
+        let owner_id = env::predecessor_account_id();
+        let mut account_ids = match self.approvals_for_all.get(&owner_id) {
+            Some(account_ids) => account_ids,
+            None => return,
+        };
+
+        let initial_storage_usage = env::storage_usage();
+        if !account_ids.remove(&account_id) {
+            return;
+        }
+        if account_ids.is_empty() {
+            self.approvals_for_all.remove(&owner_id);
+        } else {
+            self.approvals_for_all.insert(&owner_id, &account_ids);
+        }
+        let storage_released = initial_storage_usage.saturating_sub(env::storage_usage());
+        if storage_released > 0 {
+            Promise::new(owner_id.clone())
+                .transfer(storage_released as Balance * env::storage_byte_cost());
+        }
+
+        emit_event("revoke_for_all", json!({ "owner_id": owner_id, "account_id": account_id }));
+    }
+
+    pub fn nft_is_approved_for_all(&self, owner_id: AccountId, account_id: AccountId) -> bool {
+        self.internal_is_approved_for_all(&owner_id, &account_id)
+    }
+
+    /// Accounts approved for all tokens of `owner_id`.
+    pub fn nft_approvals_for_all(
+        &self,
+        owner_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<AccountId> {
+        let account_ids = match self.approvals_for_all.get(&owner_id) {
+            Some(account_ids) => account_ids,
+            None => return vec![],
+        };
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        account_ids.iter().skip(start_index as usize).take(limit).collect()
+    }
+}
@@ -0,0 +1,85 @@
+use crate::*;
+use near_sdk::{
+    near_bindgen,
+    serde::{Deserialize, Serialize},
+};
+
+/// Same shape as `DATA_IMAGE_SVG_ICON`.
+const PASS_PATH: &str = "M13.3866 157.467L160.015 75.543L200.383 215.639L292.401 36.3624L379.217 21.8194L385.004 101.962L412.167 15.5799L511.452 0L269.243 316.555L340.479 480.403L206.019 512.458L167.734 413.616L102.728 512.458L3 480.403L100.061 331.701L13.3866 157.467Z";
+const TIER_COLORS: [&str; 4] = ["d512f6", "f6c312", "12d5f6", "f61251"];
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct PassAttributes {
+    pub level: u32,
+    pub season: u32,
+}
+
+impl Default for PassAttributes {
+    fn default() -> Self {
+        Self { level: 1, season: 1 }
+    }
+}
+
+fn encode_svg(svg: &str) -> String {
+    svg.replace('%', "%25").replace('<', "%3C").replace('>', "%3E").replace('#', "%23")
+}
+
+impl Contract {
+    pub(crate) fn internal_pass_attributes(&self, token_id: &TokenId) -> PassAttributes {
+        self.pass_attributes.get(token_id).unwrap_or_default()
+    }
+
+    /// Artwork of `token_id` as a `data:` URI, drawn from its tier, level and season.
+    pub(crate) fn internal_render_svg(&self, token_id: &TokenId) -> String {
+        let tier = self.internal_tier(token_id);
+        let attributes = self.internal_pass_attributes(token_id);
+        let color = TIER_COLORS[tier as usize % TIER_COLORS.len()];
+        let svg = format!(
+            "<svg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 512 640'>\
+            <rect width='512' height='640' fill='#0e0e16'/>\
+            <path d='{}' fill='#{}' fill-rule='evenodd'/>\
+            <text x='256' y='580' fill='#ffffff' font-family='monospace' font-size='32' text-anchor='middle'>Exverse Pass {}</text>\
+            <text x='256' y='620' fill='#{}' font-family='monospace' font-size='24' text-anchor='middle'>Season {} / Level {} / Tier {}</text>\
+            </svg>",
+            PASS_PATH, color, token_id, color, attributes.season, attributes.level, tier
+        );
+        format!("data:image/svg+xml,{}", encode_svg(&svg))
+    }
+
+    /// Puts rendered artwork into `metadata.media` when on-chain artwork is enabled.
+    pub(crate) fn internal_with_artwork(&self, mut token: Token) -> Token {
+        if self.onchain_artwork {
+            if let Some(metadata) = token.metadata.as_mut() {
+                metadata.media = Some(self.internal_render_svg(&token.token_id));
+                metadata.media_hash = None;
+            }
+        }
+        token
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Serve rendered SVG instead of IPFS media in token views. Only owner or operators.
+    pub fn set_onchain_artwork(&mut self, enabled: bool) {
+        self.assert_owner_or_operator();
+        self.onchain_artwork = enabled;
+    }
+
+    pub fn set_pass_attributes(&mut self, token_id: TokenId, attributes: PassAttributes) {
+        self.assert_owner_or_operator();
+        self.tokens.owner_by_id.get(&token_id).expect("Token not found");
+        self.pass_attributes.insert(&token_id, &attributes);
+    }
+
+    pub fn nft_pass_attributes(&self, token_id: TokenId) -> PassAttributes {
+        self.tokens.owner_by_id.get(&token_id).expect("Token not found");
+        self.internal_pass_attributes(&token_id)
+    }
+
+    /// Rendered artwork as a `data:` URI regardless of `set_onchain_artwork`.
+    pub fn nft_token_svg(&self, token_id: TokenId) -> String {
+        self.tokens.owner_by_id.get(&token_id).expect("Token not found");
+        self.internal_render_svg(&token_id)
+    }
+}
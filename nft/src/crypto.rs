@@ -0,0 +1,7 @@
+use crate::*;
+
+impl Contract {
+    pub fn verify_signature(&self, pk: String, signature: String, payload: String) -> bool {
+        signatures::verify_secp256k1(&pk, &signature, &payload)
+    }
+}
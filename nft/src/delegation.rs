@@ -0,0 +1,137 @@
+use crate::*;
+use near_contract_standards::non_fungible_token::refund_deposit;
+use near_sdk::{
+    assert_one_yocto,
+    json_types::U128,
+    near_bindgen,
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+};
+
+/// Usage rights `holder_id` gave to a delegate: every pass it holds when
+/// `token_ids` is `None`, only the listed ones otherwise.
+#[derive(Serialize, Deserialize)]
+pub struct Delegation {
+    pub holder_id: AccountId,
+    pub token_ids: Option<Vec<TokenId>>,
+}
+
+impl Contract {
+    fn internal_is_delegate(
+        &self,
+        holder_id: &AccountId,
+        delegate_id: &AccountId,
+        token_id: Option<&TokenId>,
+    ) -> bool {
+        let scope = match self.delegations.get(delegate_id).and_then(|d| d.get(holder_id)) {
+            Some(scope) => scope,
+            None => return false,
+        };
+        match (token_id, scope) {
+            (None, scope) => scope.is_none(),
+            (Some(token_id), scope) => {
+                self.tokens.owner_by_id.get(token_id).as_ref() == Some(holder_id)
+                    && match scope {
+                        Some(token_ids) => token_ids.contains(token_id),
+                        None => true,
+                    }
+            }
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Delegate usage rights of all passes, or only `token_ids`, of the predecessor to
+    /// `delegate_id`. Replaces a previous delegation to the same account.
+    #[payable]
+    pub fn delegate(&mut self, delegate_id: AccountId, token_ids: Option<Vec<TokenId>>) {
+        require!(env::attached_deposit() >= 1, "Requires attached deposit of at least 1 yoctoNEAR");
+        let holder_id = env::predecessor_account_id();
+        require!(holder_id != delegate_id, "ERR_DELEGATE_SELF");
+        if let Some(token_ids) = &token_ids {
+            require!(!token_ids.is_empty(), "ERR_NO_TOKENS");
+            for token_id in token_ids.iter() {
+                let owner_id = self.tokens.owner_by_id.get(token_id).expect("Token not found");
+                require!(owner_id == holder_id, "ERR_NOT_TOKEN_OWNER");
+            }
+        }
+
+        let initial_storage_usage = env::storage_usage();
+        let mut delegations = self.delegations.get(&delegate_id).unwrap_or_else(|| {
+            UnorderedMap::new(StorageKey::DelegationsPerDelegate {
+                account_hash: env::sha256(delegate_id.as_bytes()),
+            })
+        });
+        delegations.insert(&holder_id, &token_ids);
+        self.delegations.insert(&delegate_id, &delegations);
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+
+        emit_event(
+            "delegate",
+            json!({ "holder_id": holder_id, "delegate_id": delegate_id, "token_ids": token_ids }),
+        );
+    }
+
+    /// Revoke a delegation of the predecessor, refunding its storage.
+    #[payable]
+    pub fn revoke_delegation(&mut self, delegate_id: AccountId) {
+        assert_one_yocto();
+        let holder_id = env::predecessor_account_id();
+        let initial_storage_usage = env::storage_usage();
+        let mut delegations = self.delegations.get(&delegate_id).expect("ERR_NO_DELEGATION");
+        require!(delegations.remove(&holder_id).is_some(), "ERR_NO_DELEGATION");
+        if delegations.is_empty() {
+            self.delegations.remove(&delegate_id);
+        } else {
+            self.delegations.insert(&delegate_id, &delegations);
+        }
+        let storage_released = initial_storage_usage.saturating_sub(env::storage_usage());
+        if storage_released > 0 {
+            Promise::new(holder_id.clone())
+                .transfer(storage_released as Balance * env::storage_byte_cost());
+        }
+
+        emit_event(
+            "revoke_delegation",
+            json!({ "holder_id": holder_id, "delegate_id": delegate_id }),
+        );
+    }
+
+    /// Paginated holders that delegated usage rights to `account_id`.
+    pub fn delegations_for(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<Delegation> {
+        let delegations = match self.delegations.get(&account_id) {
+            Some(delegations) => delegations,
+            None => return vec![],
+        };
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        require!(
+            (delegations.len() as u128) >= start_index,
+            "Out of bounds, please use a smaller from_index."
+        );
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        delegations
+            .iter()
+            .skip(start_index as usize)
+            .take(limit)
+            .map(|(holder_id, token_ids)| Delegation { holder_id, token_ids })
+            .collect()
+    }
+
+    /// Whether `delegate_id` may use `token_id` of `holder_id`. Without `token_id`
+    /// only a delegation of all passes counts.
+    pub fn is_delegate(
+        &self,
+        holder_id: AccountId,
+        delegate_id: AccountId,
+        token_id: Option<TokenId>,
+    ) -> bool {
+        self.internal_is_delegate(&holder_id, &delegate_id, token_id.as_ref())
+    }
+}
@@ -0,0 +1,28 @@
+use crate::*;
+use near_sdk::{json_types::U128, near_bindgen};
+
+#[near_bindgen]
+impl NonFungibleTokenEnumeration for Contract {
+    fn nft_total_supply(&self) -> U128 {
+        self.tokens.nft_total_supply()
+    }
+
+    fn nft_tokens(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Token> {
+        let tokens = self.tokens.nft_tokens(from_index, limit);
+        tokens.into_iter().map(|token| self.internal_with_artwork(token)).collect()
+    }
+
+    fn nft_supply_for_owner(&self, account_id: AccountId) -> U128 {
+        self.tokens.nft_supply_for_owner(account_id)
+    }
+
+    fn nft_tokens_for_owner(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<Token> {
+        let tokens = self.tokens.nft_tokens_for_owner(account_id, from_index, limit);
+        tokens.into_iter().map(|token| self.internal_with_artwork(token)).collect()
+    }
+}
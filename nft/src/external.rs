@@ -23,3 +23,36 @@ trait NonFungibleToken {
     // view method
     fn nft_token(&self, token_id: String) -> Option<Token>;
 }
+
+#[ext_contract(ext_approval_receiver)]
+trait NonFungibleTokenApprovalReceiver {
+    fn nft_on_approve(
+        &mut self,
+        token_id: TokenId,
+        owner_id: AccountId,
+        approval_id: u64,
+        msg: String,
+    ) -> PromiseOrValue<String>;
+}
+
+#[ext_contract(ext_receiver)]
+trait NonFungibleTokenReceiver {
+    fn nft_on_transfer(
+        &mut self,
+        sender_id: AccountId,
+        previous_owner_id: AccountId,
+        token_id: TokenId,
+        msg: String,
+    ) -> PromiseOrValue<bool>;
+}
+
+#[ext_contract(ext_self)]
+trait TransferResolver {
+    fn nft_resolve_transfer(
+        &mut self,
+        previous_owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approved_account_ids: Option<HashMap<AccountId, u64>>,
+    ) -> bool;
+}
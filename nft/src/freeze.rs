@@ -0,0 +1,73 @@
+use crate::*;
+use near_sdk::{
+    json_types::{U128, U64},
+    near_bindgen,
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct FrozenToken {
+    pub token_id: TokenId,
+    pub reason: String,
+    pub frozen_at: U64,
+}
+
+impl Contract {
+    pub(crate) fn is_frozen(&self, token_id: &TokenId) -> bool {
+        self.frozen_tokens.get(token_id).is_some()
+    }
+
+    pub(crate) fn internal_freeze(&mut self, token_id: &TokenId, reason: String) {
+        self.tokens.owner_by_id.get(token_id).expect("Token not found");
+        require!(!self.is_frozen(token_id), "ERR_TOKEN_FROZEN");
+        self.internal_remove_listing(token_id);
+        let frozen = FrozenToken {
+            token_id: token_id.clone(),
+            reason,
+            frozen_at: env::block_timestamp().into(),
+        };
+        self.frozen_tokens.insert(token_id, &frozen);
+        emit_event("freeze", json!({ "token_id": token_id, "reason": frozen.reason }));
+    }
+
+    pub(crate) fn internal_unfreeze(&mut self, token_id: &TokenId) {
+        require!(self.frozen_tokens.remove(token_id).is_some(), "ERR_TOKEN_NOT_FROZEN");
+        emit_event("unfreeze", json!({ "token_id": token_id }));
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Halt every movement of `token_id` until unfrozen, e.g. while a dispute
+    /// is resolved. Drops its listing. Only owner.
+    pub fn nft_freeze(&mut self, token_id: TokenId, reason: String) {
+        self.assert_owner();
+        self.internal_freeze(&token_id, reason);
+    }
+
+    pub fn nft_unfreeze(&mut self, token_id: TokenId) {
+        self.assert_owner();
+        require!(self.recovery_requests.get(&token_id).is_none(), "ERR_RECOVERY_PENDING");
+        self.internal_unfreeze(&token_id);
+    }
+
+    pub fn nft_frozen(&self, token_id: TokenId) -> Option<FrozenToken> {
+        self.frozen_tokens.get(&token_id)
+    }
+
+    pub fn nft_frozen_tokens(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<FrozenToken> {
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        require!(
+            (self.frozen_tokens.len() as u128) >= start_index,
+            "Out of bounds, please use a smaller from_index."
+        );
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        self.frozen_tokens.values().skip(start_index as usize).take(limit).collect()
+    }
+}
@@ -0,0 +1,161 @@
+use crate::*;
+use near_contract_standards::non_fungible_token::{
+    events::{NftBurn, NftMint},
+    refund_deposit,
+};
+use near_sdk::{
+    near_bindgen,
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+};
+
+/// Burning `input_count` passes of `input_tier` mints one pass of `output_tier`.
+/// `{token_id}` in `metadata.reference` is replaced with the id of the minted pass.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct FusionRecipe {
+    pub input_tier: u8,
+    pub input_count: u32,
+    pub output_tier: u8,
+    pub metadata: TokenMetadata,
+}
+
+impl Contract {
+    pub(crate) fn internal_tier(&self, token_id: &TokenId) -> u8 {
+        self.token_tiers.get(token_id).unwrap_or_default()
+    }
+
+    /// Removes `token_id` with everything the contract keeps about it.
+    pub(crate) fn internal_burn(&mut self, owner_id: &AccountId, token_id: &TokenId) {
+        self.internal_remove_listing(token_id);
+        self.internal_remove_user(token_id);
+        self.tokens.owner_by_id.remove(token_id);
+        if let Some(token_metadata_by_id) = &mut self.tokens.token_metadata_by_id {
+            token_metadata_by_id.remove(token_id);
+        }
+        if let Some(tokens_per_owner) = &mut self.tokens.tokens_per_owner {
+            let mut token_ids = tokens_per_owner.get(owner_id).expect("Token not found");
+            token_ids.remove(token_id);
+            if token_ids.is_empty() {
+                tokens_per_owner.remove(owner_id);
+            } else {
+                tokens_per_owner.insert(owner_id, &token_ids);
+            }
+        }
+        if let Some(approvals_by_id) = &mut self.tokens.approvals_by_id {
+            approvals_by_id.remove(token_id);
+        }
+        if let Some(next_approval_id_by_id) = &mut self.tokens.next_approval_id_by_id {
+            next_approval_id_by_id.remove(token_id);
+        }
+        self.approval_expires_at.remove(token_id);
+        self.token_tiers.remove(token_id);
+        self.pass_attributes.remove(token_id);
+        self.internal_remove_traits(token_id);
+        if let Some(request) = self.recovery_requests.get(token_id) {
+            self.internal_remove_recovery(token_id, request.froze_token);
+        }
+        self.internal_sync_holder(owner_id);
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Add or replace a fusion recipe. Only owner or operators.
+    pub fn set_fusion_recipe(&mut self, recipe_id: String, recipe: FusionRecipe) {
+        self.assert_owner_or_operator();
+        require!(recipe.input_count > 0, "ERR_WRONG_RECIPE:INPUT");
+        require!(recipe.output_tier > recipe.input_tier, "ERR_WRONG_RECIPE:TIER");
+        self.fusion_recipes.insert(&recipe_id, &recipe);
+    }
+
+    pub fn remove_fusion_recipe(&mut self, recipe_id: String) {
+        self.assert_owner_or_operator();
+        self.fusion_recipes.remove(&recipe_id);
+    }
+
+    pub fn fusion_recipes(&self) -> Vec<(String, FusionRecipe)> {
+        self.fusion_recipes.to_vec()
+    }
+
+    pub fn nft_tier(&self, token_id: TokenId) -> u8 {
+        self.tokens.owner_by_id.get(&token_id).expect("Token not found");
+        self.internal_tier(&token_id)
+    }
+
+    /// Burn `token_ids` according to `recipe_id` and mint the resulting pass to
+    /// their owner. Inputs must share one owner and the predecessor must be
+    /// allowed to move each of them. Attached deposit covers storage, if any,
+    /// storage released by the burn is refunded to the predecessor.
+    #[payable]
+    pub fn nft_fuse(&mut self, recipe_id: String, token_ids: Vec<TokenId>) -> Token {
+        let recipe = self.fusion_recipes.get(&recipe_id).expect("ERR_NO_RECIPE");
+        require!(token_ids.len() == recipe.input_count as usize, "ERR_WRONG_INPUT_COUNT");
+        let mut unique_ids = token_ids.clone();
+        unique_ids.sort();
+        unique_ids.dedup();
+        require!(unique_ids.len() == token_ids.len(), "ERR_DUPLICATED_INPUT");
+
+        let sender_id = env::predecessor_account_id();
+        let owner_id = self.tokens.owner_by_id.get(&token_ids[0]).expect("Token not found");
+        for token_id in token_ids.iter() {
+            let token_owner_id = self.tokens.owner_by_id.get(token_id).expect("Token not found");
+            require!(token_owner_id == owner_id, "ERR_MIXED_OWNERS");
+            self.assert_transfer_allowed(token_id);
+            require!(self.is_authorized(&sender_id, &owner_id, token_id), "ERR_NOT_APPROVED");
+            require!(self.internal_tier(token_id) == recipe.input_tier, "ERR_WRONG_TIER");
+        }
+
+        // listing and rental storage goes back to whoever paid for it
+        for token_id in token_ids.iter() {
+            self.internal_remove_listing(token_id);
+            self.internal_remove_user(token_id);
+        }
+        let initial_storage_usage = env::storage_usage();
+        for token_id in token_ids.iter() {
+            self.internal_burn(&owner_id, token_id);
+        }
+        NftBurn {
+            owner_id: &owner_id,
+            token_ids: &token_ids.iter().map(|id| id.as_str()).collect::<Vec<_>>(),
+            authorized_id: if sender_id != owner_id { Some(&sender_id) } else { None },
+            memo: Some(&recipe_id),
+        }
+        .emit();
+
+        self.next_fused_token_id += 1;
+        let token_id = format!("F{}", self.next_fused_token_id);
+        let mut metadata = recipe.metadata;
+        metadata.issued_at = Some(env::block_timestamp().to_string());
+        metadata.reference =
+            metadata.reference.map(|reference| reference.replace("{token_id}", &token_id));
+        let token = self.internal_mint_without_refund(
+            token_id.clone(),
+            owner_id.clone(),
+            Some(metadata),
+            None,
+        );
+        self.token_tiers.insert(&token_id, &recipe.output_tier);
+        self.internal_sync_holder(&owner_id);
+        NftMint { owner_id: &owner_id, token_ids: &[&token_id], memo: Some(&recipe_id) }.emit();
+        let storage_usage = env::storage_usage();
+        if storage_usage >= initial_storage_usage {
+            refund_deposit(storage_usage - initial_storage_usage);
+        } else {
+            let storage_released = (initial_storage_usage - storage_usage) as Balance;
+            Promise::new(sender_id.clone())
+                .transfer(storage_released * env::storage_byte_cost() + env::attached_deposit());
+        }
+
+        emit_event(
+            "fuse",
+            json!({
+                "recipe_id": recipe_id,
+                "owner_id": owner_id,
+                "burnt_token_ids": token_ids,
+                "token_id": token_id,
+                "tier": recipe.output_tier,
+            }),
+        );
+        token
+    }
+}
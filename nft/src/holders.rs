@@ -0,0 +1,110 @@
+use crate::*;
+use near_sdk::{
+    json_types::{Base58CryptoHash, U128},
+    near_bindgen,
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+};
+
+/// Merkle root of holder balances at `block_height`, built off-chain from
+/// `nft_holders` and committed at `timestamp`.
+///
+/// Leaves are `sha256("{account_id}:{balance}")` ordered by account id, inner
+/// nodes hash the sorted pair of their children and an odd node is carried
+/// to the next level as is.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct HolderSnapshot {
+    pub block_height: u64,
+    pub timestamp: u64,
+    pub holders: u64,
+    pub root: Base58CryptoHash,
+}
+
+impl Contract {
+    /// Adds `account_id` to `holders` or removes it depending on whether it
+    /// still owns any token.
+    pub(crate) fn internal_sync_holder(&mut self, account_id: &AccountId) {
+        if self.internal_holder_balance(account_id) > 0 {
+            self.holders.insert(account_id);
+        } else {
+            self.holders.remove(account_id);
+        }
+    }
+
+    pub(crate) fn internal_holder_balance(&self, account_id: &AccountId) -> u64 {
+        self.tokens
+            .tokens_per_owner
+            .as_ref()
+            .and_then(|tokens_per_owner| tokens_per_owner.get(account_id))
+            .map_or(0, |token_ids| token_ids.len())
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Number of accounts owning at least one pass.
+    pub fn nft_holders_count(&self) -> u64 {
+        self.holders.len()
+    }
+
+    /// Paginated `(owner, balance)` pairs of current holders.
+    pub fn nft_holders(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<(AccountId, U128)> {
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        require!(
+            (self.holders.len() as u128) >= start_index,
+            "Out of bounds, please use a smaller from_index."
+        );
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        self.holders
+            .iter()
+            .skip(start_index as usize)
+            .take(limit)
+            .map(|account_id| {
+                let balance = self.internal_holder_balance(&account_id) as u128;
+                (account_id, balance.into())
+            })
+            .collect()
+    }
+
+    /// Record a Merkle root of holder balances at `block_height`. The tree is too
+    /// large to build in one call, so it is built off-chain. Only owner or operators.
+    pub fn snapshot_holders(
+        &mut self,
+        block_height: u64,
+        holders: u64,
+        root: Base58CryptoHash,
+    ) -> HolderSnapshot {
+        self.assert_owner_or_operator();
+        require!(block_height <= env::block_height(), "ERR_SNAPSHOT_IN_FUTURE");
+        let count = self.holder_snapshots.len();
+        if let Some(last) = count.checked_sub(1).and_then(|i| self.holder_snapshots.get(i)) {
+            require!(last.block_height < block_height, "ERR_SNAPSHOT_OUT_OF_ORDER");
+        }
+        let snapshot =
+            HolderSnapshot { block_height, timestamp: env::block_timestamp(), holders, root };
+        self.holder_snapshots.push(&snapshot);
+        emit_event(
+            "holder_snapshot",
+            json!({
+                "index": self.holder_snapshots.len() - 1,
+                "block_height": snapshot.block_height,
+                "holders": snapshot.holders,
+                "root": snapshot.root,
+            }),
+        );
+        snapshot
+    }
+
+    pub fn holder_snapshot(&self, index: u64) -> Option<HolderSnapshot> {
+        self.holder_snapshots.get(index)
+    }
+
+    pub fn holder_snapshots_count(&self) -> u64 {
+        self.holder_snapshots.len()
+    }
+}
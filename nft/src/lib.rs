@@ -1,15 +1,18 @@
-use std::convert::TryInto;
+use std::collections::HashMap;
 
 use near_contract_standards::non_fungible_token::{
+    approval::NonFungibleTokenApproval,
+    core::{NonFungibleTokenCore, NonFungibleTokenResolver},
+    enumeration::NonFungibleTokenEnumeration,
     metadata::{
         NFTContractMetadata, NonFungibleTokenMetadataProvider, TokenMetadata, NFT_METADATA_SPEC,
     },
     NonFungibleToken, Token, TokenId,
 };
-use near_sdk::collections::LazyOption;
+use near_sdk::collections::{LazyOption, LookupMap, LookupSet};
 use near_sdk::{
     borsh::{self, BorshDeserialize, BorshSerialize},
-    collections::UnorderedSet,
+    collections::{UnorderedMap, UnorderedSet, Vector},
     require,
 };
 use near_sdk::{
@@ -17,15 +20,47 @@ use near_sdk::{
     Promise, PromiseOrValue,
 };
 
+pub use crate::artwork::PassAttributes;
+pub use crate::delegation::Delegation;
+pub(crate) use crate::events::*;
 pub use crate::external::*;
+pub use crate::freeze::FrozenToken;
+pub use crate::fusion::FusionRecipe;
+pub use crate::holders::HolderSnapshot;
+pub use crate::market::Listing;
+use crate::migrate::ContractV1;
+pub use crate::mints::MintedTokens;
 pub use crate::payout::Royalties;
+pub use crate::recovery::{RecoveryConfig, RecoveryRequest};
+pub use crate::rental::TokenUser;
+pub use crate::staking::StakerView;
+pub use crate::traits::TokenTrait;
 pub use crate::utils::*;
+pub use crate::voucher::MintVoucher;
+mod approval;
+mod approval_for_all;
+mod artwork;
+mod crypto;
+mod delegation;
+mod enumeration;
+mod events;
 mod external;
+mod freeze;
+mod fusion;
+mod holders;
+mod market;
+mod migrate;
 mod mint;
 mod mints;
 mod owner;
 mod payout;
+mod recovery;
+mod rental;
+mod staking;
+mod traits;
+mod transfer;
 mod utils;
+mod voucher;
 
 const DATA_IMAGE_SVG_ICON: &str = "data:image/svg+xml,%3Csvg xmlns='http://www.w3.org/2000/svg' viewBox='0 0 512 512' xml:space='preserve'%3E%3Cdefs/%3E%3CclipPath id='ArtboardFrame'%3E%3Crect height='512' width='512' x='0' y='0'/%3E%3C/clipPath%3E%3Cg clip-path='url(%23ArtboardFrame)'%3E%3Cpath d='M13.3866 157.467L160.015 75.543L200.383 215.639L292.401 36.3624L379.217 21.8194L385.004 101.962L412.167 15.5799L511.452 0L269.243 316.555L340.479 480.403L206.019 512.458L167.734 413.616L102.728 512.458L3 480.403L100.061 331.701L13.3866 157.467Z' fill='%23d512f6' fill-rule='evenodd' opacity='1' stroke='none'/%3E%3C/g%3E%3C/svg%3E";
 
@@ -38,6 +73,32 @@ pub struct Contract {
     tokens: NonFungibleToken,
     operators: UnorderedSet<AccountId>,
     metadata: LazyOption<NFTContractMetadata>,
+    holders: UnorderedSet<AccountId>,
+    holder_snapshots: Vector<HolderSnapshot>,
+    approval_expires_at: LookupMap<TokenId, HashMap<u64, u64>>,
+    approvals_for_all: LookupMap<AccountId, UnorderedSet<AccountId>>,
+    listings: UnorderedMap<TokenId, Listing>,
+    fusion_recipes: UnorderedMap<String, FusionRecipe>,
+    token_tiers: LookupMap<TokenId, u8>,
+    next_fused_token_id: u64,
+    staked_at: LookupMap<TokenId, u64>,
+    staked_per_owner: LookupMap<AccountId, UnorderedSet<TokenId>>,
+    stakers: UnorderedMap<AccountId, staking::Staker>,
+    points_per_second: u128,
+    points_index: u128,
+    points_index_updated_at: u64,
+    token_users: LookupMap<TokenId, TokenUser>,
+    delegations: LookupMap<AccountId, UnorderedMap<AccountId, Option<Vec<TokenId>>>>,
+    voucher_signer_pk: Option<String>,
+    used_voucher_nonces: LookupSet<u64>,
+    pass_attributes: LookupMap<TokenId, PassAttributes>,
+    onchain_artwork: bool,
+    token_traits: LookupMap<TokenId, Vec<TokenTrait>>,
+    trait_tokens: LookupMap<(String, String), UnorderedSet<TokenId>>,
+    trait_values: UnorderedMap<String, UnorderedSet<String>>,
+    frozen_tokens: UnorderedMap<TokenId, FrozenToken>,
+    recovery_config: RecoveryConfig,
+    recovery_requests: UnorderedMap<TokenId, RecoveryRequest>,
 }
 
 #[derive(BorshSerialize, BorshStorageKey)]
@@ -49,6 +110,30 @@ enum StorageKey {
     Approval,
     Royalties,
     Operator,
+    Holders,
+    HolderSnapshots,
+    ApprovalExpiry,
+    ApprovalsForAll,
+    ApprovalsForAllPerOwner { account_hash: Vec<u8> },
+    Listings,
+    FusionRecipes,
+    TokenTiers,
+    StakedAt,
+    StakedTokens,
+    StakedPerOwner { account_hash: Vec<u8> },
+    Stakers,
+    TokenUsers,
+    Delegations,
+    DelegationsPerDelegate { account_hash: Vec<u8> },
+    UsedVoucherNonces,
+    PassAttributes,
+    TokenTraits,
+    TraitTokens,
+    TraitTokensPerValue { trait_hash: Vec<u8> },
+    TraitValues,
+    TraitValuesPerType { trait_type_hash: Vec<u8> },
+    FrozenTokens,
+    RecoveryRequests,
 }
 
 #[near_bindgen]
@@ -97,7 +182,7 @@ impl Contract {
     ) -> Self {
         assert!(!env::state_exists(), "Already initialized");
         metadata.assert_valid();
-        Self {
+        Self::from(ContractV1 {
             next_token_id: 1,
             max_supply,
             tokens: NonFungibleToken::new(
@@ -110,14 +195,10 @@ impl Contract {
             royalties: LazyOption::new(StorageKey::Royalties, royalties.as_ref()),
             metadata: LazyOption::new(StorageKey::Metadata, Some(&metadata)),
             operators: UnorderedSet::new(StorageKey::Operator),
-        }
+        })
     }
 }
 
-near_contract_standards::impl_non_fungible_token_core!(Contract, tokens);
-near_contract_standards::impl_non_fungible_token_approval!(Contract, tokens);
-near_contract_standards::impl_non_fungible_token_enumeration!(Contract, tokens);
-
 #[near_bindgen]
 impl NonFungibleTokenMetadataProvider for Contract {
     fn nft_metadata(&self) -> NFTContractMetadata {
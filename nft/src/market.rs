@@ -0,0 +1,132 @@
+use crate::*;
+use near_contract_standards::non_fungible_token::refund_deposit;
+use near_sdk::{
+    assert_one_yocto,
+    json_types::U128,
+    near_bindgen,
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct Listing {
+    pub token_id: TokenId,
+    pub owner_id: AccountId,
+    pub price: U128,
+    pub listed_at: u64,
+}
+
+impl Contract {
+    /// Drops the listing of `token_id`, refunding its storage to the seller.
+    pub(crate) fn internal_remove_listing(&mut self, token_id: &TokenId) -> Option<Listing> {
+        let initial_storage_usage = env::storage_usage();
+        let listing = self.listings.remove(token_id)?;
+        let storage_released = initial_storage_usage.saturating_sub(env::storage_usage());
+        if storage_released > 0 {
+            Promise::new(listing.owner_id.clone())
+                .transfer(storage_released as Balance * env::storage_byte_cost());
+        }
+        Some(listing)
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// List a token for sale at a fixed `price` in yoctoNEAR. Requires a deposit
+    /// covering the storage of the listing, refunded on delist, sale or transfer.
+    #[payable]
+    pub fn list(&mut self, token_id: TokenId, price: U128) {
+        require!(env::attached_deposit() >= 1, "Requires attached deposit of at least 1 yoctoNEAR");
+        require!(price.0 > 0, "ERR_ZERO_PRICE");
+        let owner_id = self.tokens.owner_by_id.get(&token_id).expect("Token not found");
+        require!(env::predecessor_account_id() == owner_id, "ERR_NOT_TOKEN_OWNER");
+        self.assert_transfer_allowed(&token_id);
+
+        let initial_storage_usage = env::storage_usage();
+        let listing = Listing {
+            token_id: token_id.clone(),
+            owner_id,
+            price,
+            listed_at: env::block_timestamp(),
+        };
+        self.listings.insert(&token_id, &listing);
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+
+        emit_event(
+            "list",
+            json!({ "token_id": token_id, "owner_id": listing.owner_id, "price": price }),
+        );
+    }
+
+    #[payable]
+    pub fn delist(&mut self, token_id: TokenId) {
+        assert_one_yocto();
+        let listing = self.listings.get(&token_id).expect("ERR_NOT_LISTED");
+        require!(env::predecessor_account_id() == listing.owner_id, "ERR_NOT_TOKEN_OWNER");
+        self.internal_remove_listing(&token_id);
+
+        emit_event("delist", json!({ "token_id": token_id, "owner_id": listing.owner_id }));
+    }
+
+    /// Buy a listed token. The price is split between the seller and royalty
+    /// accounts, the excess of the attached deposit is refunded.
+    #[payable]
+    pub fn buy(&mut self, token_id: TokenId) -> Token {
+        let listing = self.listings.get(&token_id).expect("ERR_NOT_LISTED");
+        let buyer_id = env::predecessor_account_id();
+        require!(buyer_id != listing.owner_id, "ERR_BUY_OWN_TOKEN");
+        let price = listing.price.0;
+        let deposit = env::attached_deposit();
+        require!(deposit >= price, "ERR_NOT_ENOUGH");
+
+        self.internal_transfer(
+            &listing.owner_id,
+            &buyer_id,
+            &token_id,
+            None,
+            Some("buy".to_string()),
+        );
+        self.internal_prune_approval_expiries(&token_id);
+
+        let payout =
+            self.royalties.get().unwrap_or_default().create_payout(price, &listing.owner_id);
+        for (account_id, amount) in payout.payout.iter() {
+            if amount.0 > 0 {
+                Promise::new(account_id.clone()).transfer(amount.0);
+            }
+        }
+        if deposit > price {
+            Promise::new(buyer_id.clone()).transfer(deposit - price);
+        }
+
+        emit_event(
+            "buy",
+            json!({
+                "token_id": token_id,
+                "owner_id": listing.owner_id,
+                "buyer_id": buyer_id,
+                "price": listing.price,
+            }),
+        );
+        self.tokens.nft_token(token_id).unwrap()
+    }
+
+    pub fn listing(&self, token_id: TokenId) -> Option<Listing> {
+        self.listings.get(&token_id)
+    }
+
+    pub fn listings_count(&self) -> u64 {
+        self.listings.len()
+    }
+
+    pub fn listings(&self, from_index: Option<U128>, limit: Option<u64>) -> Vec<Listing> {
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        require!(
+            (self.listings.len() as u128) >= start_index,
+            "Out of bounds, please use a smaller from_index."
+        );
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        self.listings.values().skip(start_index as usize).take(limit).collect()
+    }
+}
@@ -0,0 +1,90 @@
+use crate::*;
+use near_sdk::{json_types::U128, near_bindgen};
+
+/// Layout of `Contract` before holders and the rest of the extensions were added.
+#[derive(BorshDeserialize)]
+pub(crate) struct ContractV1 {
+    pub next_token_id: u64,
+    pub max_supply: u64,
+    pub royalties: LazyOption<Royalties>,
+    pub tokens: NonFungibleToken,
+    pub operators: UnorderedSet<AccountId>,
+    pub metadata: LazyOption<NFTContractMetadata>,
+}
+
+impl From<ContractV1> for Contract {
+    /// Keeps the tokens of `old`, every extension starts empty.
+    fn from(old: ContractV1) -> Self {
+        Self {
+            next_token_id: old.next_token_id,
+            max_supply: old.max_supply,
+            royalties: old.royalties,
+            tokens: old.tokens,
+            operators: old.operators,
+            metadata: old.metadata,
+            holders: UnorderedSet::new(StorageKey::Holders),
+            holder_snapshots: Vector::new(StorageKey::HolderSnapshots),
+            approval_expires_at: LookupMap::new(StorageKey::ApprovalExpiry),
+            approvals_for_all: LookupMap::new(StorageKey::ApprovalsForAll),
+            listings: UnorderedMap::new(StorageKey::Listings),
+            fusion_recipes: UnorderedMap::new(StorageKey::FusionRecipes),
+            token_tiers: LookupMap::new(StorageKey::TokenTiers),
+            next_fused_token_id: 0,
+            staked_at: LookupMap::new(StorageKey::StakedAt),
+            staked_per_owner: LookupMap::new(StorageKey::StakedTokens),
+            stakers: UnorderedMap::new(StorageKey::Stakers),
+            points_per_second: 1,
+            points_index: 0,
+            points_index_updated_at: env::block_timestamp(),
+            token_users: LookupMap::new(StorageKey::TokenUsers),
+            delegations: LookupMap::new(StorageKey::Delegations),
+            voucher_signer_pk: None,
+            used_voucher_nonces: LookupSet::new(StorageKey::UsedVoucherNonces),
+            pass_attributes: LookupMap::new(StorageKey::PassAttributes),
+            onchain_artwork: false,
+            token_traits: LookupMap::new(StorageKey::TokenTraits),
+            trait_tokens: LookupMap::new(StorageKey::TraitTokens),
+            trait_values: UnorderedMap::new(StorageKey::TraitValues),
+            frozen_tokens: UnorderedMap::new(StorageKey::FrozenTokens),
+            recovery_config: RecoveryConfig::default(),
+            recovery_requests: UnorderedMap::new(StorageKey::RecoveryRequests),
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Upgrade the state of a collection deployed before the extensions. Holders are
+    /// not tracked yet, fill them in with `backfill_holders`.
+    #[private]
+    #[init(ignore_state)]
+    pub fn migrate() -> Self {
+        let old: ContractV1 = env::state_read().expect("ERR_NO_STATE");
+        old.into()
+    }
+
+    /// Add the owners of the tokens from `from_index` to `holders`, a page at a time.
+    /// Returns the number of holders. Only owner or operators.
+    pub fn backfill_holders(&mut self, from_index: Option<U128>, limit: Option<u64>) -> u64 {
+        self.assert_owner_or_operator();
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        require!(
+            (self.tokens.owner_by_id.len() as u128) >= start_index,
+            "Out of bounds, please use a smaller from_index."
+        );
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        let owners: Vec<AccountId> = self
+            .tokens
+            .owner_by_id
+            .iter()
+            .skip(start_index as usize)
+            .take(limit)
+            .map(|(_, owner_id)| owner_id)
+            .collect();
+        for owner_id in owners {
+            self.holders.insert(&owner_id);
+        }
+        self.holders.len()
+    }
+}
@@ -1,10 +1,26 @@
 use std::collections::HashMap;
 
+use crate::mint::pass_metadata;
 use crate::*;
 use near_contract_standards::non_fungible_token::{
     core::StorageKey, core::StorageKey as NftStorageKey, events::NftMint, refund_deposit_to_account,
 };
-use near_sdk::{near_bindgen, serde_json::json};
+use near_sdk::{
+    json_types::U128,
+    near_bindgen,
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+};
+
+/// Storage prefixes of the contract collections are a single `StorageKey` tag.
+const PREFIX_LEN: u64 = 1;
+
+#[derive(Serialize, Deserialize)]
+pub struct MintedTokens {
+    pub tokens: Vec<Token>,
+    /// Storage cost actually charged, the rest of the deposit is refunded.
+    pub storage_cost: U128,
+}
 
 impl Contract {
     pub fn internal_mint_without_refund(
@@ -51,54 +67,143 @@ impl Contract {
 
         Token { token_id, owner_id, metadata: token_metadata, approved_account_ids }
     }
-}
 
-#[near_bindgen]
-impl Contract {
-    #[payable]
-    pub fn nft_mints(&mut self, receiver_id: AccountId, amount: u32) -> Vec<Token> {
+    /// Mint a batch of tokens to `owner_id`. Unlike calling `internal_mint_without_refund`
+    /// per token, the owner's enumeration set is loaded and written back once and a
+    /// single `NftMint` event is logged for the whole batch.
+    pub(crate) fn internal_mint_batch(
+        &mut self,
+        owner_id: &AccountId,
+        tokens: Vec<(TokenId, TokenMetadata)>,
+    ) -> Vec<Token> {
+        let mut owner_tokens = self.tokens.tokens_per_owner.as_ref().map(|tokens_per_owner| {
+            tokens_per_owner.get(owner_id).unwrap_or_else(|| {
+                UnorderedSet::new(NftStorageKey::TokensPerOwner {
+                    account_hash: env::sha256(owner_id.as_bytes()),
+                })
+            })
+        });
+
+        let mut minted = Vec::with_capacity(tokens.len());
+        for (token_id, token_metadata) in tokens {
+            if self.tokens.owner_by_id.insert(&token_id, owner_id).is_some() {
+                env::panic_str("token_id must be unique");
+            }
+            if let Some(token_metadata_by_id) = &mut self.tokens.token_metadata_by_id {
+                token_metadata_by_id.insert(&token_id, &token_metadata);
+            }
+            if let Some(owner_tokens) = &mut owner_tokens {
+                owner_tokens.insert(&token_id);
+            }
+            let approved_account_ids =
+                if self.tokens.approvals_by_id.is_some() { Some(HashMap::new()) } else { None };
+            minted.push(Token {
+                token_id,
+                owner_id: owner_id.clone(),
+                metadata: Some(token_metadata),
+                approved_account_ids,
+            });
+        }
+
+        if let (Some(tokens_per_owner), Some(owner_tokens)) =
+            (&mut self.tokens.tokens_per_owner, &owner_tokens)
+        {
+            tokens_per_owner.insert(owner_id, owner_tokens);
+        }
+        let token_ids: Vec<&str> = minted.iter().map(|token| token.token_id.as_str()).collect();
+        NftMint { owner_id, token_ids: &token_ids, memo: None }.emit();
+        minted
+    }
+
+    /// Upper bound of the storage `nft_mints` uses to mint `amount` passes to `receiver_id`:
+    /// per pass its owner record and tree node, its metadata and its enumeration entry,
+    /// plus the receiver's enumeration set and holder entry if it holds no pass yet.
+    pub(crate) fn internal_mint_storage_bound(&self, receiver_id: &AccountId, amount: u32) -> u64 {
+        let account_len = receiver_id.as_str().len() as u64;
+        let owner_set_prefix =
+            NftStorageKey::TokensPerOwner { account_hash: env::sha256(receiver_id.as_bytes()) }
+                .try_to_vec()
+                .unwrap();
+        // the set stores its elements and their index under the prefix and a tag
+        let owner_set_prefix_len = owner_set_prefix.len() as u64 + 1;
+        let mut bytes = 0;
+        for token_id in self.next_token_id..self.next_token_id + amount as u64 {
+            let id_len = token_id.to_string().len() as u64;
+            let metadata =
+                TokenMetadata { issued_at: Some(u64::MAX.to_string()), ..pass_metadata(token_id) };
+            // owner_by_id: value record, tree node and the link to it from its parent
+            bytes += record(PREFIX_LEN + 1 + 4 + id_len, 4 + account_len);
+            bytes += record(PREFIX_LEN + 1 + 8, 8 + 4 + id_len + 9 + 9 + 8) + 8;
+            // token_metadata_by_id
+            bytes += record(PREFIX_LEN + 4 + id_len, metadata.try_to_vec().unwrap().len() as u64);
+            // tokens_per_owner set: element and its index
+            bytes += record(owner_set_prefix_len + 8, 4 + id_len);
+            bytes += record(owner_set_prefix_len + 4 + id_len, 8);
+        }
+        let new_owner_set = self
+            .tokens
+            .tokens_per_owner
+            .as_ref()
+            .is_some_and(|tokens_per_owner| !tokens_per_owner.contains_key(receiver_id));
+        if new_owner_set {
+            let owner_set = UnorderedSet::<TokenId>::new(owner_set_prefix);
+            bytes +=
+                record(PREFIX_LEN + 4 + account_len, owner_set.try_to_vec().unwrap().len() as u64);
+        }
+        if !self.holders.contains(receiver_id) {
+            bytes += record(PREFIX_LEN + 1 + 8, 4 + account_len);
+            bytes += record(PREFIX_LEN + 1 + 4 + account_len, 8);
+        }
+        bytes
+    }
+
+    /// Mints `amount` passes to `receiver_id` and refunds the attached deposit left after
+    /// the measured storage usage to the predecessor.
+    fn internal_nft_mints(&mut self, receiver_id: AccountId, amount: u32) -> MintedTokens {
         self.assert_owner_or_operator();
+        assert!(amount <= MAX_MINT_BATCH, "ERR_BATCH_TOO_LARGE");
         assert!(
             self.next_token_id + amount as u64 - 1 as u64 <= self.max_supply,
             "Player, try again next time"
         );
         let initial_storage_usage = env::storage_usage();
-        let tokens = (0..amount)
-            .map(|_| {
-                let token_id = self.next_token_id;
-                let token_metadata = TokenMetadata {
-                    title: Some("Exverse Pass".to_string()),
-                    description: None,
-                    media: Some("QmTWewETfuHsP3EXJ6zYh1Us6uFs75rXnvyk2ktbidhZmu".to_string()),
-                    media_hash: None,
-                    copies: Some(1),
-                    issued_at: Some(env::block_timestamp().to_string()),
-                    expires_at: None,
-                    starts_at: None,
-                    updated_at: None,
-                    extra: None,
-                    reference: Some(
-                        format!("QmcjcieB2WvqEQiviJUsfdQ8FqMJT78kobbJgnxE2iK3DG/{}", token_id)
-                            .to_string(),
-                    ),
-                    reference_hash: None,
-                };
-                let token = self.internal_mint_without_refund(
-                    token_id.to_string(),
-                    receiver_id.clone(),
-                    Some(token_metadata),
-                    Some(env::predecessor_account_id()),
-                );
-                self.next_token_id += 1;
-                NftMint { owner_id: &token.owner_id, token_ids: &[&token.token_id], memo: None }
-                    .emit();
-                token
-            })
-            .collect();
-        refund_deposit_to_account(
-            env::storage_usage() - initial_storage_usage,
-            env::predecessor_account_id(),
+        let first_token_id = self.next_token_id;
+        self.next_token_id += amount as u64;
+        let tokens = self.internal_mint_batch(
+            &receiver_id,
+            (first_token_id..self.next_token_id)
+                .map(|token_id| (token_id.to_string(), pass_metadata(token_id)))
+                .collect(),
         );
-        tokens
+        self.internal_sync_holder(&receiver_id);
+        let storage_used = env::storage_usage() - initial_storage_usage;
+        refund_deposit_to_account(storage_used, env::predecessor_account_id());
+        MintedTokens {
+            tokens,
+            storage_cost: U128(storage_used as Balance * env::storage_byte_cost()),
+        }
+    }
+}
+
+/// Storage usage of a record: the runtime charges 40 bytes on top of key and value.
+fn record(key_len: u64, value_len: u64) -> u64 {
+    key_len + value_len + 40
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Deposit that always covers `nft_mints(receiver_id, amount)` with the current supply.
+    pub fn mint_storage_cost(&self, receiver_id: AccountId, amount: u32) -> U128 {
+        U128(
+            self.internal_mint_storage_bound(&receiver_id, amount) as Balance
+                * env::storage_byte_cost(),
+        )
+    }
+
+    /// Mint `amount` passes to `receiver_id`, returning them with the storage cost charged.
+    /// The rest of the attached deposit is refunded to the predecessor.
+    #[payable]
+    pub fn nft_mints(&mut self, receiver_id: AccountId, amount: u32) -> MintedTokens {
+        self.internal_nft_mints(receiver_id, amount)
     }
 }
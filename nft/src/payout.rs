@@ -21,7 +21,7 @@ use std::collections::HashMap;
 /// This currently deviates from the standard but is in the process of updating to use this type
 #[derive(Default, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct Payout {
-    payout: HashMap<AccountId, U128>,
+    pub(crate) payout: HashMap<AccountId, U128>,
 }
 
 pub trait Payouts {
@@ -63,6 +63,7 @@ impl Payouts for Contract {
         max_len_payout: Option<u32>,
     ) -> Payout {
         assert_one_yocto();
+        self.assert_transfer_allowed(&token_id);
         let payout = self.nft_payout(token_id.clone(), balance, max_len_payout);
         self.nft_transfer(receiver_id.clone(), token_id.clone(), approval_id.clone(), memo.clone());
         payout
@@ -95,7 +96,7 @@ impl Royalties {
         });
         require!(total <= 100, "total percent of each royalty split  must be less than 100")
     }
-    fn create_payout(&self, balance: Balance, owner_id: &AccountId) -> Payout {
+    pub(crate) fn create_payout(&self, balance: Balance, owner_id: &AccountId) -> Payout {
         let royalty_payment = apply_percent(self.percent, balance);
         let mut payout = Payout {
             payout: self
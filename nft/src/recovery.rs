@@ -0,0 +1,222 @@
+use crate::*;
+use near_contract_standards::non_fungible_token::{events::NftTransfer, refund_deposit};
+use near_sdk::{
+    assert_one_yocto,
+    json_types::{U128, U64},
+    near_bindgen,
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct RecoveryConfig {
+    /// Distinct owner or operator approvals needed.
+    pub threshold: u8,
+    /// Seconds the holder has after the last approval before the pass can be moved.
+    pub delay_sec: u32,
+}
+
+impl Default for RecoveryConfig {
+    fn default() -> Self {
+        Self { threshold: 1, delay_sec: 3 * 24 * 60 * 60 }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct RecoveryRequest {
+    pub token_id: TokenId,
+    pub claimant_id: AccountId,
+    pub holder_id: AccountId,
+    pub reason: String,
+    pub filed_at: U64,
+    pub approvals: Vec<AccountId>,
+    /// Set once enough approvals are collected.
+    pub executable_at: Option<U64>,
+    /// Whether the first approval froze the pass, otherwise it was frozen before.
+    pub froze_token: bool,
+}
+
+impl Contract {
+    /// Drops the request of `token_id` and optionally unfreezes the pass, sending
+    /// the released storage to the claimant.
+    pub(crate) fn internal_remove_recovery(
+        &mut self,
+        token_id: &TokenId,
+        unfreeze: bool,
+    ) -> RecoveryRequest {
+        let initial_storage_usage = env::storage_usage();
+        let request = self.recovery_requests.remove(token_id).expect("ERR_NO_RECOVERY");
+        if unfreeze && self.is_frozen(token_id) {
+            self.internal_unfreeze(token_id);
+        }
+        let storage_released = initial_storage_usage.saturating_sub(env::storage_usage());
+        if storage_released > 0 {
+            Promise::new(request.claimant_id.clone())
+                .transfer(storage_released as Balance * env::storage_byte_cost());
+        }
+        request
+    }
+
+    /// Moves `token_id` regardless of approvals, guards and stake.
+    fn internal_force_transfer(&mut self, from: &AccountId, to: &AccountId, token_id: &TokenId) {
+        if self.is_staked(token_id) {
+            self.internal_unstake(from, std::slice::from_ref(token_id));
+        }
+        self.tokens.internal_transfer_unguarded(token_id, from, to);
+        if let Some(approvals_by_id) = &mut self.tokens.approvals_by_id {
+            approvals_by_id.remove(token_id);
+        }
+        self.approval_expires_at.remove(token_id);
+        self.on_transfer(from, to, token_id);
+        NftTransfer {
+            old_owner_id: from,
+            new_owner_id: to,
+            token_ids: &[token_id],
+            authorized_id: Some(&self.tokens.owner_id),
+            memo: Some("recovery"),
+        }
+        .emit();
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    pub fn set_recovery_config(&mut self, config: RecoveryConfig) {
+        self.assert_owner();
+        require!(config.threshold > 0, "ERR_WRONG_THRESHOLD");
+        self.recovery_config = config;
+    }
+
+    pub fn recovery_config(&self) -> RecoveryConfig {
+        self.recovery_config.clone()
+    }
+
+    /// Claim that `token_id` was lost or stolen from the predecessor. The pass stays
+    /// movable until the first approval. Attached deposit covers storage.
+    #[payable]
+    pub fn file_recovery(&mut self, token_id: TokenId, reason: String) {
+        require!(env::attached_deposit() >= 1, "Requires attached deposit of at least 1 yoctoNEAR");
+        let claimant_id = env::predecessor_account_id();
+        let holder_id = self.tokens.owner_by_id.get(&token_id).expect("Token not found");
+        require!(holder_id != claimant_id, "ERR_CLAIM_OWN_TOKEN");
+        require!(self.recovery_requests.get(&token_id).is_none(), "ERR_RECOVERY_EXISTS");
+
+        let initial_storage_usage = env::storage_usage();
+        let request = RecoveryRequest {
+            token_id: token_id.clone(),
+            claimant_id,
+            holder_id,
+            reason,
+            filed_at: env::block_timestamp().into(),
+            approvals: vec![],
+            executable_at: None,
+            froze_token: false,
+        };
+        self.recovery_requests.insert(&token_id, &request);
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+
+        emit_event(
+            "recovery_filed",
+            json!({
+                "token_id": token_id,
+                "claimant_id": request.claimant_id,
+                "holder_id": request.holder_id,
+                "reason": request.reason,
+            }),
+        );
+    }
+
+    /// Approve a recovery request. The first approval freezes the pass until the request
+    /// is executed or cancelled. Once `threshold` owner or operator approvals are in,
+    /// the pass can be moved after `delay_sec`. Only owner or operators.
+    pub fn approve_recovery(&mut self, token_id: TokenId) {
+        self.assert_owner_or_operator();
+        let account_id = env::predecessor_account_id();
+        let mut request = self.recovery_requests.get(&token_id).expect("ERR_NO_RECOVERY");
+        require!(!request.approvals.contains(&account_id), "ERR_ALREADY_APPROVED");
+        if request.approvals.is_empty() {
+            // the pass could move while the request was pending
+            request.holder_id = self.tokens.owner_by_id.get(&token_id).expect("Token not found");
+            if !self.is_frozen(&token_id) {
+                self.internal_freeze(&token_id, format!("recovery: {}", request.reason));
+                request.froze_token = true;
+            }
+        }
+        request.approvals.push(account_id.clone());
+        if request.executable_at.is_none()
+            && request.approvals.len() >= self.recovery_config.threshold as usize
+        {
+            let delay = self.recovery_config.delay_sec as u64 * 1_000_000_000;
+            request.executable_at = Some((env::block_timestamp() + delay).into());
+        }
+        self.recovery_requests.insert(&token_id, &request);
+
+        emit_event(
+            "recovery_approval",
+            json!({
+                "token_id": token_id,
+                "account_id": account_id,
+                "approvals": request.approvals.len(),
+                "executable_at": request.executable_at,
+            }),
+        );
+    }
+
+    /// Move the pass to the claimant once the request is approved and the waiting
+    /// period passed. Only owner.
+    pub fn execute_recovery(&mut self, token_id: TokenId) {
+        self.assert_owner();
+        let request = self.recovery_requests.get(&token_id).expect("ERR_NO_RECOVERY");
+        let executable_at = request.executable_at.expect("ERR_RECOVERY_NOT_APPROVED");
+        require!(env::block_timestamp() >= executable_at.0, "ERR_RECOVERY_WAITING");
+
+        let request = self.internal_remove_recovery(&token_id, true);
+        self.internal_force_transfer(&request.holder_id, &request.claimant_id, &token_id);
+
+        emit_event(
+            "recovery_executed",
+            json!({
+                "token_id": token_id,
+                "claimant_id": request.claimant_id,
+                "holder_id": request.holder_id,
+            }),
+        );
+    }
+
+    /// Withdraw a request as the claimant or reject it as owner or operator.
+    /// Unfreezes the pass if its approval froze it.
+    #[payable]
+    pub fn cancel_recovery(&mut self, token_id: TokenId) {
+        assert_one_yocto();
+        let request = self.recovery_requests.get(&token_id).expect("ERR_NO_RECOVERY");
+        require!(
+            env::predecessor_account_id() == request.claimant_id || self.is_owner_or_operators(),
+            "ERR_NOT_CLAIMANT"
+        );
+        self.internal_remove_recovery(&token_id, request.froze_token);
+
+        emit_event(
+            "recovery_cancelled",
+            json!({ "token_id": token_id, "account_id": env::predecessor_account_id() }),
+        );
+    }
+
+    pub fn recovery_request(&self, token_id: TokenId) -> Option<RecoveryRequest> {
+        self.recovery_requests.get(&token_id)
+    }
+
+    pub fn recovery_requests(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<RecoveryRequest> {
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        require!(
+            (self.recovery_requests.len() as u128) >= start_index,
+            "Out of bounds, please use a smaller from_index."
+        );
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        self.recovery_requests.values().skip(start_index as usize).take(limit).collect()
+    }
+}
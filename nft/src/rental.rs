@@ -0,0 +1,89 @@
+use crate::*;
+use near_contract_standards::non_fungible_token::refund_deposit;
+use near_sdk::{
+    assert_one_yocto,
+    json_types::U64,
+    near_bindgen,
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+};
+
+/// Account allowed to use a pass until `expires_at` (nanoseconds) without owning it.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct TokenUser {
+    pub user_id: AccountId,
+    pub expires_at: U64,
+    /// Paid the storage of the assignment, refunded when it is removed.
+    pub payer_id: AccountId,
+}
+
+impl Contract {
+    fn assert_can_set_user(&self, token_id: &TokenId) {
+        let owner_id = self.tokens.owner_by_id.get(token_id).expect("Token not found");
+        require!(
+            self.is_authorized(&env::predecessor_account_id(), &owner_id, token_id),
+            "ERR_NOT_APPROVED"
+        );
+    }
+
+    /// Drops the user of `token_id`, refunding its storage to whoever set it.
+    pub(crate) fn internal_remove_user(&mut self, token_id: &TokenId) -> Option<TokenUser> {
+        let initial_storage_usage = env::storage_usage();
+        let user = self.token_users.remove(token_id)?;
+        let storage_released = initial_storage_usage.saturating_sub(env::storage_usage());
+        if storage_released > 0 {
+            Promise::new(user.payer_id.clone())
+                .transfer(storage_released as Balance * env::storage_byte_cost());
+        }
+        Some(user)
+    }
+
+    /// Current user of `token_id`, expired assignments are treated as absent.
+    pub(crate) fn internal_token_user(&self, token_id: &TokenId) -> Option<TokenUser> {
+        self.token_users.get(token_id).filter(|user| user.expires_at.0 > env::block_timestamp())
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Let `user_id` use `token_id` until `expires_at` (nanoseconds). Callable by the
+    /// owner or an approved account, the assignment is cleared on transfer.
+    #[payable]
+    pub fn nft_set_user(&mut self, token_id: TokenId, user_id: AccountId, expires_at: U64) {
+        require!(env::attached_deposit() >= 1, "Requires attached deposit of at least 1 yoctoNEAR");
+        require!(expires_at.0 > env::block_timestamp(), "ERR_EXPIRES_IN_PAST");
+        self.assert_can_set_user(&token_id);
+
+        // the previous assignment's storage goes back to whoever paid for it
+        self.internal_remove_user(&token_id);
+        let initial_storage_usage = env::storage_usage();
+        let payer_id = env::predecessor_account_id();
+        self.token_users
+            .insert(&token_id, &TokenUser { user_id: user_id.clone(), expires_at, payer_id });
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+
+        emit_event(
+            "update_user",
+            json!({ "token_id": token_id, "user_id": user_id, "expires_at": expires_at }),
+        );
+    }
+
+    /// Remove the user of `token_id`, refunding the storage of the assignment.
+    #[payable]
+    pub fn nft_clear_user(&mut self, token_id: TokenId) {
+        assert_one_yocto();
+        self.assert_can_set_user(&token_id);
+        if self.internal_remove_user(&token_id).is_some() {
+            emit_event("update_user", json!({ "token_id": token_id, "user_id": null }));
+        }
+    }
+
+    /// Account that may use `token_id` right now, `None` if not rented out.
+    pub fn nft_user_of(&self, token_id: TokenId) -> Option<AccountId> {
+        self.internal_token_user(&token_id).map(|user| user.user_id)
+    }
+
+    pub fn nft_user_expires(&self, token_id: TokenId) -> Option<U64> {
+        self.internal_token_user(&token_id).map(|user| user.expires_at)
+    }
+}
@@ -0,0 +1,178 @@
+use crate::*;
+use near_contract_standards::non_fungible_token::refund_deposit;
+use near_sdk::{
+    assert_one_yocto,
+    json_types::U128,
+    near_bindgen,
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+};
+
+/// Timestamps are in nanoseconds, so points are tracked multiplied by 10^9.
+const POINTS_SCALE: u128 = 1_000_000_000;
+
+#[derive(BorshSerialize, BorshDeserialize, Default)]
+pub struct Staker {
+    staked: u64,
+    scaled_points: u128,
+    paid_index: u128,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct StakerView {
+    pub staked: u64,
+    pub points: U128,
+}
+
+impl Contract {
+    pub(crate) fn is_staked(&self, token_id: &TokenId) -> bool {
+        self.staked_at.contains_key(token_id)
+    }
+
+    /// Scaled points a single token earned if staked since the contract was deployed.
+    fn internal_points_index(&self) -> u128 {
+        let elapsed = env::block_timestamp() - self.points_index_updated_at;
+        self.points_index + self.points_per_second * elapsed as u128
+    }
+
+    fn internal_update_points_index(&mut self) {
+        self.points_index = self.internal_points_index();
+        self.points_index_updated_at = env::block_timestamp();
+    }
+
+    fn internal_settled_staker(&self, account_id: &AccountId) -> Staker {
+        let mut staker = self.stakers.get(account_id).unwrap_or_default();
+        let index = self.internal_points_index();
+        staker.scaled_points += staker.staked as u128 * (index - staker.paid_index);
+        staker.paid_index = index;
+        staker
+    }
+
+    fn internal_staked_tokens(&self, account_id: &AccountId) -> UnorderedSet<TokenId> {
+        self.staked_per_owner.get(account_id).unwrap_or_else(|| {
+            UnorderedSet::new(StorageKey::StakedPerOwner {
+                account_hash: env::sha256(account_id.as_bytes()),
+            })
+        })
+    }
+
+    /// Unlocks `token_ids`, refunding the storage they took to `account_id`.
+    pub(crate) fn internal_unstake(&mut self, account_id: &AccountId, token_ids: &[TokenId]) {
+        let initial_storage_usage = env::storage_usage();
+        let mut staker = self.internal_settled_staker(account_id);
+        let mut staked_tokens = self.internal_staked_tokens(account_id);
+        for token_id in token_ids.iter() {
+            require!(staked_tokens.remove(token_id), "ERR_TOKEN_NOT_STAKED");
+            self.staked_at.remove(token_id);
+            staker.staked -= 1;
+        }
+        if staked_tokens.is_empty() {
+            self.staked_per_owner.remove(account_id);
+        } else {
+            self.staked_per_owner.insert(account_id, &staked_tokens);
+        }
+        self.stakers.insert(account_id, &staker);
+        let storage_released = initial_storage_usage.saturating_sub(env::storage_usage());
+        if storage_released > 0 {
+            Promise::new(account_id.clone())
+                .transfer(storage_released as Balance * env::storage_byte_cost());
+        }
+
+        emit_event("unstake", json!({ "account_id": account_id, "token_ids": token_ids }));
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Lock tokens of the predecessor to accrue points. Staked tokens can't be
+    /// transferred, listed or fused. Attached deposit covers storage.
+    #[payable]
+    pub fn stake(&mut self, token_ids: Vec<TokenId>) {
+        require!(env::attached_deposit() >= 1, "Requires attached deposit of at least 1 yoctoNEAR");
+        let account_id = env::predecessor_account_id();
+        let initial_storage_usage = env::storage_usage();
+
+        let mut staker = self.internal_settled_staker(&account_id);
+        let mut staked_tokens = self.internal_staked_tokens(&account_id);
+        for token_id in token_ids.iter() {
+            let owner_id = self.tokens.owner_by_id.get(token_id).expect("Token not found");
+            require!(owner_id == account_id, "ERR_NOT_TOKEN_OWNER");
+            require!(!self.is_staked(token_id), "ERR_TOKEN_STAKED");
+            self.internal_remove_listing(token_id);
+            self.staked_at.insert(token_id, &env::block_timestamp());
+            staked_tokens.insert(token_id);
+            staker.staked += 1;
+        }
+        self.staked_per_owner.insert(&account_id, &staked_tokens);
+        self.stakers.insert(&account_id, &staker);
+        refund_deposit(env::storage_usage().saturating_sub(initial_storage_usage));
+
+        emit_event("stake", json!({ "account_id": account_id, "token_ids": token_ids }));
+    }
+
+    #[payable]
+    pub fn unstake(&mut self, token_ids: Vec<TokenId>) {
+        assert_one_yocto();
+        let account_id = env::predecessor_account_id();
+        for token_id in token_ids.iter() {
+            let owner_id = self.tokens.owner_by_id.get(token_id).expect("Token not found");
+            require!(owner_id == account_id, "ERR_NOT_TOKEN_OWNER");
+        }
+        self.internal_unstake(&account_id, &token_ids);
+    }
+
+    /// Points accrued per second by each staked token. Only owner or operators.
+    pub fn set_points_per_second(&mut self, points_per_second: U128) {
+        self.assert_owner_or_operator();
+        self.internal_update_points_index();
+        self.points_per_second = points_per_second.0;
+    }
+
+    pub fn points_per_second(&self) -> U128 {
+        self.points_per_second.into()
+    }
+
+    pub fn staker(&self, account_id: AccountId) -> StakerView {
+        let staker = self.internal_settled_staker(&account_id);
+        StakerView { staked: staker.staked, points: (staker.scaled_points / POINTS_SCALE).into() }
+    }
+
+    /// Accounts that ever staked with their current stake and points, for leaderboards.
+    pub fn stakers(
+        &self,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<(AccountId, StakerView)> {
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        self.stakers
+            .keys()
+            .skip(start_index as usize)
+            .take(limit)
+            .map(|account_id| {
+                let staker = self.staker(account_id.clone());
+                (account_id, staker)
+            })
+            .collect()
+    }
+
+    pub fn staked_tokens(
+        &self,
+        account_id: AccountId,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<TokenId> {
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        self.staked_per_owner.get(&account_id).map_or(vec![], |token_ids| {
+            token_ids.iter().skip(start_index as usize).take(limit).collect()
+        })
+    }
+
+    /// Timestamp in nanoseconds the token was staked at, `None` if it isn't staked.
+    pub fn staked_at(&self, token_id: TokenId) -> Option<u64> {
+        self.staked_at.get(&token_id)
+    }
+}
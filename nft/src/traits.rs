@@ -0,0 +1,143 @@
+use crate::*;
+use near_sdk::{
+    json_types::U128,
+    near_bindgen,
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+};
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Clone)]
+pub struct TokenTrait {
+    pub trait_type: String,
+    pub value: String,
+}
+
+impl Contract {
+    pub(crate) fn internal_remove_traits(&mut self, token_id: &TokenId) {
+        for token_trait in self.token_traits.remove(token_id).unwrap_or_default() {
+            let key = (token_trait.trait_type, token_trait.value);
+            let mut token_ids = self.trait_tokens.get(&key).expect("ERR_TRAIT_INDEX");
+            token_ids.remove(token_id);
+            if !token_ids.is_empty() {
+                self.trait_tokens.insert(&key, &token_ids);
+                continue;
+            }
+            self.trait_tokens.remove(&key);
+            let mut values = self.trait_values.get(&key.0).expect("ERR_TRAIT_INDEX");
+            values.remove(&key.1);
+            if values.is_empty() {
+                self.trait_values.remove(&key.0);
+            } else {
+                self.trait_values.insert(&key.0, &values);
+            }
+        }
+    }
+
+    /// Replaces the traits of `token_id`, keeps the per-trait indexes and
+    /// `metadata.extra` in sync.
+    pub(crate) fn internal_set_traits(&mut self, token_id: &TokenId, traits: Vec<TokenTrait>) {
+        for (i, token_trait) in traits.iter().enumerate() {
+            require!(
+                traits[..i].iter().all(|t| t.trait_type != token_trait.trait_type),
+                "ERR_DUPLICATE_TRAIT"
+            );
+        }
+        self.internal_remove_traits(token_id);
+
+        for token_trait in traits.iter() {
+            let key = (token_trait.trait_type.clone(), token_trait.value.clone());
+            let mut token_ids = self.trait_tokens.get(&key).unwrap_or_else(|| {
+                UnorderedSet::new(StorageKey::TraitTokensPerValue {
+                    trait_hash: env::sha256(&key.try_to_vec().unwrap()),
+                })
+            });
+            token_ids.insert(token_id);
+            self.trait_tokens.insert(&key, &token_ids);
+
+            let mut values = self.trait_values.get(&key.0).unwrap_or_else(|| {
+                UnorderedSet::new(StorageKey::TraitValuesPerType {
+                    trait_type_hash: env::sha256(key.0.as_bytes()),
+                })
+            });
+            values.insert(&key.1);
+            self.trait_values.insert(&key.0, &values);
+        }
+
+        if let Some(token_metadata_by_id) = &mut self.tokens.token_metadata_by_id {
+            let mut metadata = token_metadata_by_id.get(token_id).expect("Token not found");
+            metadata.extra = match traits.is_empty() {
+                true => None,
+                false => Some(json!({ "attributes": traits }).to_string()),
+            };
+            metadata.updated_at = Some(env::block_timestamp().to_string());
+            token_metadata_by_id.insert(token_id, &metadata);
+        }
+        if !traits.is_empty() {
+            self.token_traits.insert(token_id, &traits);
+        }
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Set or reveal traits of already minted passes. Only owner or operators.
+    pub fn set_token_traits(&mut self, traits: Vec<(TokenId, Vec<TokenTrait>)>) {
+        self.assert_owner_or_operator();
+        for (token_id, token_traits) in traits {
+            self.tokens.owner_by_id.get(&token_id).expect("Token not found");
+            self.internal_set_traits(&token_id, token_traits);
+        }
+    }
+
+    pub fn nft_traits(&self, token_id: TokenId) -> Vec<TokenTrait> {
+        self.token_traits.get(&token_id).unwrap_or_default()
+    }
+
+    pub fn nft_trait_types(&self) -> Vec<String> {
+        self.trait_values.keys().collect()
+    }
+
+    /// Number of passes for every value of `trait_type`, rarest first.
+    pub fn nft_trait_counts(&self, trait_type: String) -> Vec<(String, u64)> {
+        let values = match self.trait_values.get(&trait_type) {
+            Some(values) => values,
+            None => return vec![],
+        };
+        let mut counts: Vec<(String, u64)> = values
+            .iter()
+            .map(|value| {
+                let key = (trait_type.clone(), value);
+                let count = self.trait_tokens.get(&key).map_or(0, |token_ids| token_ids.len());
+                (key.1, count)
+            })
+            .collect();
+        counts.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+        counts
+    }
+
+    pub fn nft_tokens_by_trait(
+        &self,
+        trait_type: String,
+        value: String,
+        from_index: Option<U128>,
+        limit: Option<u64>,
+    ) -> Vec<Token> {
+        let token_ids = match self.trait_tokens.get(&(trait_type, value)) {
+            Some(token_ids) => token_ids,
+            None => return vec![],
+        };
+        let start_index: u128 = from_index.map(From::from).unwrap_or_default();
+        require!(
+            (token_ids.len() as u128) >= start_index,
+            "Out of bounds, please use a smaller from_index."
+        );
+        let limit = limit.map(|v| v as usize).unwrap_or(usize::MAX);
+        require!(limit != 0, "Cannot provide limit of 0.");
+        token_ids
+            .iter()
+            .skip(start_index as usize)
+            .take(limit)
+            .filter_map(|token_id| self.nft_token(token_id))
+            .collect()
+    }
+}
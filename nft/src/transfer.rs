@@ -0,0 +1,170 @@
+use crate::*;
+use near_contract_standards::non_fungible_token::events::NftTransfer;
+use near_sdk::{assert_one_yocto, near_bindgen};
+use std::collections::HashMap;
+
+impl Contract {
+    /// Guards shared by every path that moves or burns `token_id`.
+    pub(crate) fn assert_transfer_allowed(&self, token_id: &TokenId) {
+        require!(!self.is_staked(token_id), "ERR_TOKEN_STAKED");
+        require!(!self.is_frozen(token_id), "ERR_TOKEN_FROZEN");
+    }
+
+    /// Whether `sender_id` may move `token_id` of `owner_id` without an explicit approval id.
+    pub(crate) fn is_authorized(
+        &self,
+        sender_id: &AccountId,
+        owner_id: &AccountId,
+        token_id: &TokenId,
+    ) -> bool {
+        sender_id == owner_id
+            || self.internal_is_approved_for_all(owner_id, sender_id)
+            || (self.tokens.nft_is_approved(token_id.clone(), sender_id.clone(), None)
+                && !self.internal_approval_expired(token_id, sender_id))
+    }
+
+    /// Moves `token_id` to `receiver_id` on behalf of `sender_id`, who is either the owner,
+    /// approved for all of the owner's tokens or holds a per-token approval.
+    /// Returns the previous owner and approvals like `NonFungibleToken::internal_transfer`.
+    pub(crate) fn internal_transfer(
+        &mut self,
+        sender_id: &AccountId,
+        receiver_id: &AccountId,
+        token_id: &TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) -> (AccountId, Option<HashMap<AccountId, u64>>) {
+        let owner_id = self.tokens.owner_by_id.get(token_id).expect("Token not found");
+        self.assert_transfer_allowed(token_id);
+        let result =
+            if sender_id != &owner_id && self.internal_is_approved_for_all(&owner_id, sender_id) {
+                // the standard transfer only knows per-token approvals, log the operator itself
+                let approved_account_ids =
+                    self.tokens.approvals_by_id.as_mut().and_then(|by_id| by_id.remove(token_id));
+                require!(&owner_id != receiver_id, "Current and next owner must differ");
+                self.tokens.internal_transfer_unguarded(token_id, &owner_id, receiver_id);
+                NftTransfer {
+                    old_owner_id: &owner_id,
+                    new_owner_id: receiver_id,
+                    token_ids: &[token_id],
+                    authorized_id: Some(sender_id),
+                    memo: memo.as_deref(),
+                }
+                .emit();
+                (owner_id.clone(), approved_account_ids)
+            } else {
+                if sender_id != &owner_id {
+                    require!(
+                        !self.internal_approval_expired(token_id, sender_id),
+                        "ERR_APPROVAL_EXPIRED"
+                    );
+                }
+                self.tokens.internal_transfer(sender_id, receiver_id, token_id, approval_id, memo)
+            };
+        self.on_transfer(&owner_id, receiver_id, token_id);
+        result
+    }
+
+    /// Bookkeeping shared by every path that moves a token between accounts,
+    /// including the way back of a reverted `nft_transfer_call`.
+    pub(crate) fn on_transfer(
+        &mut self,
+        old_owner_id: &AccountId,
+        new_owner_id: &AccountId,
+        token_id: &TokenId,
+    ) {
+        self.internal_sync_holder(old_owner_id);
+        self.internal_sync_holder(new_owner_id);
+        self.internal_remove_listing(token_id);
+        self.internal_remove_user(token_id);
+    }
+}
+
+#[near_bindgen]
+impl NonFungibleTokenCore for Contract {
+    #[payable]
+    fn nft_transfer(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+    ) {
+        assert_one_yocto();
+        let sender_id = env::predecessor_account_id();
+        self.internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo);
+        self.internal_prune_approval_expiries(&token_id);
+    }
+
+    #[payable]
+    fn nft_transfer_call(
+        &mut self,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approval_id: Option<u64>,
+        memo: Option<String>,
+        msg: String,
+    ) -> PromiseOrValue<bool> {
+        assert_one_yocto();
+        require!(
+            env::prepaid_gas() > GAS_FOR_NFT_TRANSFER_CALL + GAS_FOR_RESOLVE_TRANSFER,
+            "More gas is required"
+        );
+        let sender_id = env::predecessor_account_id();
+        // Approval expiries are pruned in `nft_resolve_transfer`, the previous owner's
+        // approvals come back if the receiver rejects the token.
+        let (old_owner_id, old_approvals) =
+            self.internal_transfer(&sender_id, &receiver_id, &token_id, approval_id, memo);
+        ext_receiver::nft_on_transfer(
+            sender_id,
+            old_owner_id.clone(),
+            token_id.clone(),
+            msg,
+            receiver_id.clone(),
+            NO_DEPOSIT,
+            env::prepaid_gas() - GAS_FOR_NFT_TRANSFER_CALL - GAS_FOR_RESOLVE_TRANSFER,
+        )
+        .then(ext_self::nft_resolve_transfer(
+            old_owner_id,
+            receiver_id,
+            token_id,
+            old_approvals,
+            env::current_account_id(),
+            NO_DEPOSIT,
+            GAS_FOR_RESOLVE_TRANSFER,
+        ))
+        .into()
+    }
+
+    fn nft_token(&self, token_id: TokenId) -> Option<Token> {
+        self.tokens.nft_token(token_id).map(|token| self.internal_with_artwork(token))
+    }
+}
+
+#[near_bindgen]
+impl NonFungibleTokenResolver for Contract {
+    #[private]
+    fn nft_resolve_transfer(
+        &mut self,
+        previous_owner_id: AccountId,
+        receiver_id: AccountId,
+        token_id: TokenId,
+        approved_account_ids: Option<HashMap<AccountId, u64>>,
+    ) -> bool {
+        let transferred = self.tokens.nft_resolve_transfer(
+            previous_owner_id.clone(),
+            receiver_id.clone(),
+            token_id.clone(),
+            approved_account_ids,
+        );
+        self.internal_prune_approval_expiries(&token_id);
+        if !transferred {
+            // the receiver could stake the token before handing it back
+            if self.is_staked(&token_id) {
+                self.internal_unstake(&receiver_id, std::slice::from_ref(&token_id));
+            }
+            self.on_transfer(&receiver_id, &previous_owner_id, &token_id);
+        }
+        transferred
+    }
+}
@@ -7,5 +7,8 @@ pub const TGAS: Gas = Gas(10u64.pow(12));
 pub const GAS_FOR_RESOLVE_TRANSFER: Gas = Gas(5_000_000_000_000);
 pub const GAS_FOR_FT_TRANSFER_CALL: Gas = Gas(30_000_000_000_000);
 pub const GAS_FOR_NFT_TRANSFER_CALL: Gas = Gas(25_000_000_000_000);
+pub const GAS_FOR_NFT_APPROVE: Gas = Gas(10_000_000_000_000);
 pub const GAS_FOR_NFT_MINT_CALL: Gas = Gas(55_000_000_000_000);
+/// Most passes `nft_mints` mints in one call, keeps a batch well within 300 TGas.
+pub const MAX_MINT_BATCH: u32 = 40;
 pub const MIN_ATTACHED_DEPOSIT: u128 = 100_000_000_000_000_000_000_000;
@@ -0,0 +1,107 @@
+use crate::mint::pass_metadata;
+use crate::*;
+use near_contract_standards::non_fungible_token::events::NftMint;
+use near_sdk::{
+    json_types::{U128, U64},
+    near_bindgen,
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+};
+
+/// Permission issued off-chain by `voucher_signer_pk` to mint one pass.
+#[derive(Serialize, Deserialize)]
+pub struct MintVoucher {
+    pub receiver_id: AccountId,
+    pub tier: u8,
+    pub price: U128,
+    pub expires_at: U64,
+    pub nonce: U64,
+}
+
+impl MintVoucher {
+    /// Signed payload, bound to the contract so a voucher can't be replayed on another deployment.
+    pub fn payload(&self, contract_id: &AccountId) -> String {
+        format!(
+            "{}:{}:{}:{}:{}:{}",
+            contract_id, self.receiver_id, self.tier, self.price.0, self.expires_at.0, self.nonce.0
+        )
+    }
+}
+
+#[near_bindgen]
+impl Contract {
+    /// Set the secp256k1 public key (hex, compressed) vouchers are signed with.
+    /// Only owner.
+    pub fn set_voucher_signer(&mut self, signer_pk: Option<String>) {
+        self.assert_owner();
+        if let Some(signer_pk) = &signer_pk {
+            signatures::parse_secp256k1(signer_pk);
+        }
+        self.voucher_signer_pk = signer_pk;
+    }
+
+    pub fn voucher_signer(&self) -> Option<String> {
+        self.voucher_signer_pk.clone()
+    }
+
+    pub fn is_voucher_used(&self, nonce: U64) -> bool {
+        self.used_voucher_nonces.contains(&nonce.0)
+    }
+
+    /// Mint a pass described by a signed voucher. Attached deposit must cover the
+    /// voucher price and storage, the rest is refunded. The price goes to the owner.
+    #[payable]
+    pub fn nft_mint_voucher(&mut self, voucher: MintVoucher, signature: String) -> Token {
+        let signer_pk = self.voucher_signer_pk.clone().expect("ERR_NOT_VALID_SIGNER");
+        require!(
+            self.verify_signature(
+                signer_pk,
+                signature,
+                voucher.payload(&env::current_account_id())
+            ),
+            "ERR_WRONG_SIG:PAYLOAD"
+        );
+        require!(voucher.expires_at.0 > env::block_timestamp(), "ERR_VOUCHER_EXPIRED");
+        require!(self.next_token_id <= self.max_supply, "Player, try again next time");
+
+        let initial_storage_usage = env::storage_usage();
+        require!(self.used_voucher_nonces.insert(&voucher.nonce.0), "ERR_VOUCHER_USED");
+        let token_id = self.next_token_id;
+        let token = self.internal_mint_without_refund(
+            token_id.to_string(),
+            voucher.receiver_id.clone(),
+            Some(pass_metadata(token_id)),
+            None,
+        );
+        self.next_token_id += 1;
+        if voucher.tier > 0 {
+            self.token_tiers.insert(&token.token_id, &voucher.tier);
+        }
+        self.internal_sync_holder(&voucher.receiver_id);
+        NftMint { owner_id: &token.owner_id, token_ids: &[&token.token_id], memo: None }.emit();
+
+        let storage_cost =
+            (env::storage_usage() - initial_storage_usage) as Balance * env::storage_byte_cost();
+        let cost = voucher.price.0 + storage_cost;
+        require!(env::attached_deposit() >= cost, format!("Must attach {} yoctoNEAR", cost));
+        if voucher.price.0 > 0 {
+            Promise::new(self.tokens.owner_id.clone()).transfer(voucher.price.0);
+        }
+        let refund = env::attached_deposit() - cost;
+        if refund > 1 {
+            Promise::new(env::predecessor_account_id()).transfer(refund);
+        }
+
+        emit_event(
+            "voucher_mint",
+            json!({
+                "token_id": token.token_id,
+                "receiver_id": voucher.receiver_id,
+                "tier": voucher.tier,
+                "price": voucher.price,
+                "nonce": voucher.nonce,
+            }),
+        );
+        token
+    }
+}
@@ -0,0 +1,46 @@
+//! Signature checks shared by the nft and halloffame contracts. Signatures are
+//! 64 bytes in hex, malformed keys panic with `ERR_WRONG_PKEY:*`.
+use std::convert::{TryFrom, TryInto};
+
+use ed25519_dalek::Verifier;
+use near_sdk::env::keccak256;
+use near_sdk::CurveType;
+
+/// Compressed secp256k1 public key in hex.
+pub fn parse_secp256k1(pk: &str) -> secp256k1::PublicKey {
+    let mut pk_slice: [u8; 33] = [0; 33];
+    hex::decode_to_slice(pk, &mut pk_slice).expect("ERR_WRONG_PKEY:HEX");
+    secp256k1::PublicKey::parse_compressed(&pk_slice).expect("ERR_WRONG_PKEY:PARSE")
+}
+
+/// Ed25519 public key in NEAR `ed25519:` base58 format.
+pub fn parse_ed25519(pk: &str) -> ed25519_dalek::PublicKey {
+    let pk: near_sdk::PublicKey = pk.parse().expect("ERR_WRONG_PKEY:BASE58");
+    assert!(pk.curve_type() == CurveType::ED25519, "ERR_WRONG_PKEY:CURVE");
+    ed25519_dalek::PublicKey::from_bytes(&pk.as_bytes()[1..]).expect("ERR_WRONG_PKEY:PARSE")
+}
+
+fn decode_signature(signature: &str) -> Vec<u8> {
+    let sign_vec = hex::decode(signature).expect("ERR_WRONG_SIG:HEX");
+    assert_eq!(sign_vec.len(), 64, "ERR_WRONG_SIG:SIZE");
+    sign_vec
+}
+
+/// Whether `signature` signs the keccak256 of `payload` with `pk`.
+pub fn verify_secp256k1(pk: &str, signature: &str, payload: &str) -> bool {
+    let public_key = parse_secp256k1(pk);
+    let hash = keccak256(payload.as_bytes());
+    let msg = secp256k1::Message::parse(&hash.try_into().unwrap());
+    let sign = secp256k1::Signature::parse(&decode_signature(signature).try_into().unwrap());
+    secp256k1::verify(&msg, &sign, &public_key)
+}
+
+/// Whether `signature` signs `payload` itself with `pk`. Signatures that aren't valid
+/// ed25519 points, e.g. made by a secp256k1 key, don't verify.
+pub fn verify_ed25519(pk: &str, signature: &str, payload: &str) -> bool {
+    let public_key = parse_ed25519(pk);
+    match ed25519_dalek::Signature::try_from(decode_signature(signature).as_slice()) {
+        Ok(sign) => public_key.verify(payload.as_bytes(), &sign).is_ok(),
+        Err(_) => false,
+    }
+}
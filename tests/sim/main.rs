@@ -3,18 +3,42 @@ use std::convert::TryInto;
 
 use libsecp256k1 as secp256k1;
 use near_contract_standards::non_fungible_token::Token;
-use near_sdk::json_types::U128;
+use near_sdk::json_types::{U128, U64};
+use near_sdk::AccountId;
 use near_sdk_sim::{call, deploy, init_simulator, to_yocto, view, ContractAccount, UserAccount};
 use sha3::{Digest, Keccak256};
 
 use halloffame::{
-    Config, ContractContract as HallContract, GAS_FOR_NFT_MINT_CALL, GAS_FOR_RESOLVE_TRANSFER,
-    GAS_FOR_SACRIFICE,
+    AllowlistPermit, Config, ConfigUpdate, ContractContract as HallContract, Proceeds,
+    GAS_FOR_NFT_MINTS, GAS_FOR_NFT_MINT_CALL, GAS_FOR_RESOLVE_TRANSFER, GAS_FOR_SACRIFICE,
 };
 use nft::ContractContract as NftContract;
 
+mod test_approvals;
+mod test_artwork;
+mod test_auction;
+mod test_batch_mint;
+mod test_delegation;
+mod test_ed25519;
+mod test_freeze;
+mod test_ft_payments;
+mod test_fusion;
+mod test_holders;
+mod test_market;
 mod test_open;
+mod test_permits;
+mod test_phases;
 mod test_private;
+mod test_proceeds;
+mod test_public_limits;
+mod test_recovery;
+mod test_rental;
+mod test_revenue;
+mod test_signer_keys;
+mod test_staking;
+mod test_tiers;
+mod test_traits;
+mod test_voucher;
 
 // Load in contract bytes at runtime
 near_sdk_sim::lazy_static_include::lazy_static_include_bytes! {
@@ -74,28 +98,45 @@ impl Runner {
 
         call!(
             root,
-            hall.sudo_config(
-                None,
-                None,
-                None,
-                None,
-                None,
-                Some(hex::encode(pk.serialize_compressed()))
-            )
+            hall.sudo_config(ConfigUpdate {
+                signer_pk: Some(hex::encode(pk.serialize_compressed())),
+                ..Default::default()
+            })
         )
         .assert_success();
         Self { root, nft, hall, keypair: sk, alice, bob, eva }
     }
 
-    fn hash(&self, message: String) -> [u8; 32] {
+    pub fn signer_pk(&self) -> String {
+        hex::encode(secp256k1::PublicKey::from_secret_key(&self.keypair).serialize_compressed())
+    }
+
+    pub fn hash(&self, message: String) -> [u8; 32] {
         let mut hasher = Keccak256::new();
         hasher.update(message);
         let result = hasher.finalize();
         result.into()
     }
 
-    pub fn sign(&self, account: &UserAccount, amount: u32) -> String {
-        let message = format!("{}:{}", &account.account_id, amount);
+    /// Permit for the active phase, valid for an hour at any price.
+    pub fn permit(&self, permitted_amount: u32) -> AllowlistPermit {
+        let hall = &self.hall;
+        let config: Config = view!(hall.config()).unwrap_json();
+        AllowlistPermit {
+            contract_id: hall.account_id(),
+            phase: config.active_phase.map_or("PRIVATE".to_string(), |phase| phase.name),
+            permitted_amount,
+            token_id: None,
+            price: U128(to_yocto("1000")),
+            expires_at: self.now() / 1_000_000_000 + 3600,
+        }
+    }
+
+    pub fn sign(&self, account: &UserAccount, permit: &AllowlistPermit) -> String {
+        self.sign_message(permit.payload(&account.account_id))
+    }
+
+    pub fn sign_message(&self, message: String) -> String {
         let hash = self.hash(message);
         let msg = secp256k1::Message::parse(&hash.try_into().unwrap());
         let (sig, _) = secp256k1::sign(&msg, &self.keypair);
@@ -116,7 +157,11 @@ impl Runner {
             to @ MomentInTime::BeforePrivate => {
                 call!(
                     self.root,
-                    hall.sudo_config(None, None, None, Some(now + 100), Some(now + 110), None)
+                    hall.sudo_config(ConfigUpdate {
+                        private_sale_timestamp: Some(now + 100),
+                        open_sale_timestamp: Some(now + 110),
+                        ..Default::default()
+                    })
                 );
                 let config: Config = view!(hall.config()).unwrap_json();
                 assert_eq!(config.stage, "SOON");
@@ -124,7 +169,11 @@ impl Runner {
             to @ MomentInTime::AfterPrivate => {
                 call!(
                     self.root,
-                    hall.sudo_config(None, None, None, Some(now - 10), Some(now - 5), None)
+                    hall.sudo_config(ConfigUpdate {
+                        private_sale_timestamp: Some(now - 10),
+                        open_sale_timestamp: Some(now - 5),
+                        ..Default::default()
+                    })
                 );
                 let config: Config = view!(hall.config()).unwrap_json();
                 assert_eq!(config.stage, "OPEN");
@@ -132,7 +181,11 @@ impl Runner {
             to @ MomentInTime::InPrivate => {
                 call!(
                     self.root,
-                    hall.sudo_config(None, None, None, Some(now - 10), Some(now + 100), None)
+                    hall.sudo_config(ConfigUpdate {
+                        private_sale_timestamp: Some(now - 10),
+                        open_sale_timestamp: Some(now + 100),
+                        ..Default::default()
+                    })
                 );
                 let config: Config = view!(hall.config()).unwrap_json();
                 assert_eq!(config.stage, "PRIVATE");
@@ -153,6 +206,33 @@ impl Runner {
         assert!(is_ok, "100 NEAR - {} = {}", amount, diff);
     }
 
+    pub fn now(&self) -> u64 {
+        self.root.borrow_runtime().current_block().block_timestamp
+    }
+
+    /// Produce blocks until at least `seconds` passed.
+    pub fn wait(&self, seconds: u64) {
+        let until = self.now() + seconds * 1_000_000_000;
+        while self.now() < until {
+            self.root.borrow_runtime_mut().produce_block().unwrap();
+        }
+    }
+
+    pub fn nft_holders_count(&self) -> u64 {
+        let nft = &self.nft;
+        view!(nft.nft_holders_count()).unwrap_json()
+    }
+
+    pub fn transfer(&self, from: &UserAccount, to: &UserAccount, token_id: &str) {
+        let nft = &self.nft;
+        call!(
+            from,
+            nft.nft_transfer(to.account_id(), token_id.to_string(), None, None),
+            deposit = 1
+        )
+        .assert_success();
+    }
+
     pub fn take_out(&self, amount: u32) {
         let nft = &self.nft;
         call!(self.root, nft.nft_mints(self.root.account_id(), amount), deposit = to_yocto("1"))
@@ -161,19 +241,33 @@ impl Runner {
 
     pub fn change_price(&self, price: u128) {
         let hall = &self.hall;
-        call!(self.root, hall.sudo_config(None, None, Some(U128::from(price)), None, None, None));
+        call!(
+            self.root,
+            hall.sudo_config(ConfigUpdate {
+                price_in_yocto: Some(U128::from(price)),
+                ..Default::default()
+            })
+        );
         let config: Config = view!(hall.config()).unwrap_json();
         assert_eq!(config.price_in_yocto.0, price);
     }
 
     pub fn personal_sacrifice(&self, price: u128, amount: u32) -> bool {
-        let signature = self.sign(&self.alice, 2);
-        self.internal_sacrifice(price, amount, Some(2), Some(signature))
+        self.personal_sacrifice_permit(price, amount, self.permit(2))
     }
 
     pub fn personal_sacrifice_force(&self, price: u128, amount: u32) -> bool {
-        let signature = self.sign(&self.alice, amount);
-        self.internal_sacrifice(price, amount, Some(amount), Some(signature))
+        self.personal_sacrifice_permit(price, amount, self.permit(amount))
+    }
+
+    pub fn personal_sacrifice_permit(
+        &self,
+        price: u128,
+        amount: u32,
+        permit: AllowlistPermit,
+    ) -> bool {
+        let signature = self.sign(&self.alice, &permit);
+        self.internal_sacrifice(price, amount, Some(permit), Some(signature))
     }
 
     pub fn personal_sacrifice_signed(
@@ -183,23 +277,24 @@ impl Runner {
         permitted_amount: u32,
         sign: String,
     ) -> bool {
-        self.internal_sacrifice(price, amount, Some(permitted_amount), Some(sign))
+        self.internal_sacrifice(price, amount, Some(self.permit(permitted_amount)), Some(sign))
     }
 
     fn internal_sacrifice(
         &self,
         deposit: u128,
         amount: u32,
-        permitted_amount: Option<u32>,
+        permit: Option<AllowlistPermit>,
         sign: Option<String>,
     ) -> bool {
         let hall = &self.hall;
         let tx = call!(
             self.alice,
-            hall.sacrifice(amount, permitted_amount, sign.clone()),
+            hall.sacrifice(amount, permit, sign.clone()),
             deposit,
             GAS_FOR_RESOLVE_TRANSFER.0
                 + GAS_FOR_SACRIFICE.0
+                + GAS_FOR_NFT_MINTS.0
                 + GAS_FOR_NFT_MINT_CALL.0 * amount as u64
         );
         println!("TX: {:?}", tx);
@@ -217,6 +312,26 @@ impl Runner {
         }
     }
 
+    pub fn set_public_limits(&self, wallet_cap: u32, transaction_cap: u32, allocation: u32) {
+        let hall = &self.hall;
+        call!(
+            self.root,
+            hall.sudo_config(ConfigUpdate {
+                public_wallet_cap: Some(wallet_cap),
+                transaction_cap: Some(transaction_cap),
+                public_allocation: Some(allocation),
+                ..Default::default()
+            })
+        )
+        .assert_success();
+    }
+
+    pub fn pending_proceeds(&self, account: &UserAccount) -> u128 {
+        let hall = &self.hall;
+        let proceeds: Vec<Proceeds> = view!(hall.proceeds_of(account.account_id())).unwrap_json();
+        proceeds.iter().find(|p| p.token_id.is_none()).map_or(0, |p| p.pending.0)
+    }
+
     pub fn sacrifice(&self, deposit: u128, amount: u32) -> bool {
         self.internal_sacrifice(deposit, amount, None, None)
     }
@@ -0,0 +1,100 @@
+use crate::*;
+use near_sdk::json_types::U64;
+
+#[test]
+fn approval_expires() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(1);
+
+    let expires_at = runner.now() + 5 * 1_000_000_000;
+    call!(
+        runner.root,
+        nft.nft_approve_until("1".to_string(), runner.alice.account_id(), U64(expires_at), None),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    let approved: bool =
+        view!(nft.nft_is_approved("1".to_string(), runner.alice.account_id(), None)).unwrap_json();
+    assert!(approved);
+
+    runner.wait(10);
+    let approved: bool =
+        view!(nft.nft_is_approved("1".to_string(), runner.alice.account_id(), None)).unwrap_json();
+    assert!(!approved);
+    let tx = call!(
+        runner.alice,
+        nft.nft_transfer(runner.alice.account_id(), "1".to_string(), None, None),
+        deposit = 1
+    );
+    assert!(!tx.is_ok());
+
+    let revoked: Vec<AccountId> =
+        call!(runner.bob, nft.nft_revoke_expired("1".to_string())).unwrap_json();
+    assert_eq!(revoked, vec![runner.alice.account_id()]);
+    let expires_at: Option<U64> =
+        view!(nft.nft_approval_expires_at("1".to_string(), runner.alice.account_id()))
+            .unwrap_json();
+    assert!(expires_at.is_none());
+}
+
+#[test]
+fn approval_without_expiry() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(1);
+
+    call!(
+        runner.root,
+        nft.nft_approve("1".to_string(), runner.alice.account_id(), None),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    runner.wait(10);
+    call!(
+        runner.alice,
+        nft.nft_transfer(runner.alice.account_id(), "1".to_string(), None, None),
+        deposit = 1
+    )
+    .assert_success();
+}
+
+#[test]
+fn approval_for_all() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(2);
+
+    call!(
+        runner.root,
+        nft.nft_approve_for_all(runner.alice.account_id()),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    let approved: bool =
+        view!(nft.nft_is_approved_for_all(runner.root.account_id(), runner.alice.account_id()))
+            .unwrap_json();
+    assert!(approved);
+    let approvals: Vec<AccountId> =
+        view!(nft.nft_approvals_for_all(runner.root.account_id(), None, None)).unwrap_json();
+    assert_eq!(approvals, vec![runner.alice.account_id()]);
+
+    let tx = call!(
+        runner.alice,
+        nft.nft_transfer(runner.bob.account_id(), "1".to_string(), None, None),
+        deposit = 1
+    );
+    tx.assert_success();
+    // indexers see the operator, not the owner, as the one moving the token
+    assert!(tx.logs().iter().any(|log| log.contains("\"nft_transfer\"")
+        && log.contains(&format!("\"authorized_id\":\"{}\"", runner.alice.account_id()))));
+
+    call!(runner.root, nft.nft_revoke_for_all(runner.alice.account_id()), deposit = 1)
+        .assert_success();
+    let tx = call!(
+        runner.alice,
+        nft.nft_transfer(runner.bob.account_id(), "2".to_string(), None, None),
+        deposit = 1
+    );
+    assert!(!tx.is_ok());
+}
@@ -0,0 +1,30 @@
+use crate::*;
+use nft::PassAttributes;
+
+#[test]
+fn artwork_rendered_from_attributes() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(1);
+
+    let token: Token = view!(nft.nft_token("1".to_string())).unwrap_json();
+    assert!(!token.metadata.unwrap().media.unwrap().starts_with("data:"));
+
+    assert!(!call!(runner.alice, nft.set_onchain_artwork(true)).is_ok());
+    call!(runner.root, nft.set_onchain_artwork(true)).assert_success();
+    call!(
+        runner.root,
+        nft.set_pass_attributes("1".to_string(), PassAttributes { level: 7, season: 2 })
+    )
+    .assert_success();
+
+    let token: Token = view!(nft.nft_token("1".to_string())).unwrap_json();
+    let media = token.metadata.unwrap().media.unwrap();
+    assert!(media.starts_with("data:image/svg+xml,%3Csvg"));
+    assert!(media.contains("Season 2 / Level 7 / Tier 0"));
+
+    let tokens: Vec<Token> = view!(nft.nft_tokens(None, None)).unwrap_json();
+    assert_eq!(tokens[0].metadata.as_ref().unwrap().media, Some(media.clone()));
+    let svg: String = view!(nft.nft_token_svg("1".to_string())).unwrap_json();
+    assert_eq!(svg, media);
+}
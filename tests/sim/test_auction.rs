@@ -0,0 +1,90 @@
+use crate::test_phases::phase;
+use crate::*;
+use halloffame::{AuctionPrice, DutchAuction, Phase};
+
+fn start_auction(runner: &Runner, rebates: bool) {
+    let hall = &runner.hall;
+    let now = runner.now() / 1_000_000_000;
+    let mut auction = phase("AUCTION", now - 1, now + 10_000, "0", false);
+    auction.supply_cap = Some(2);
+    auction.dutch_auction = Some(DutchAuction {
+        start_price: U128(to_yocto("10")),
+        floor_price: U128(to_yocto("2")),
+        decay_step: U128(to_yocto("1")),
+        interval_sec: 100,
+        rebates,
+    });
+    call!(runner.root, hall.set_phases(vec![auction])).assert_success();
+}
+
+#[test]
+fn auction_price_decays() {
+    let runner = Runner::default();
+    let hall = &runner.hall;
+    start_auction(&runner, false);
+
+    let price: Option<AuctionPrice> = view!(hall.auction_price()).unwrap_json();
+    let price = price.unwrap();
+    assert_eq!(price.current.0, to_yocto("10"));
+    assert_eq!(price.next, Some(U128(to_yocto("9"))));
+
+    runner.wait(200);
+    let price: Option<AuctionPrice> = view!(hall.auction_price()).unwrap_json();
+    assert_eq!(price.unwrap().current.0, to_yocto("8"));
+    assert!(!runner.sacrifice(to_yocto("7.5"), 1));
+    assert!(runner.sacrifice(to_yocto("9"), 1));
+    runner.assert_spend_about(&runner.alice, to_yocto("8"));
+}
+
+#[test]
+fn auction_rebates_to_clearing_price() {
+    let runner = Runner::default();
+    let hall = &runner.hall;
+    start_auction(&runner, true);
+
+    assert!(runner.sacrifice(to_yocto("11"), 1));
+    runner.wait(200);
+    assert!(!call!(
+        runner.bob,
+        hall.claim_rebate("AUCTION".to_string(), runner.alice.account_id())
+    )
+    .is_ok());
+    assert!(runner.sacrifice(to_yocto("9"), 1));
+    runner.assert_spend_about(&runner.alice, to_yocto("18"));
+
+    // sold out, clearing price is 8
+    let rebate: U128 =
+        view!(hall.rebate_of("AUCTION".to_string(), runner.alice.account_id())).unwrap_json();
+    assert_eq!(rebate.0, to_yocto("2"));
+    call!(runner.bob, hall.claim_rebate("AUCTION".to_string(), runner.alice.account_id()))
+        .assert_success();
+    runner.assert_spend_about(&runner.alice, to_yocto("16"));
+    assert!(!call!(
+        runner.bob,
+        hall.claim_rebate("AUCTION".to_string(), runner.alice.account_id())
+    )
+    .is_ok());
+}
+
+#[test]
+fn auction_rebates_survive_phase_removal() {
+    let runner = Runner::default();
+    let hall = &runner.hall;
+    start_auction(&runner, true);
+
+    assert!(runner.sacrifice(to_yocto("11"), 1));
+    runner.wait(200);
+    let phases: Vec<Phase> = view!(hall.phases()).unwrap_json();
+    let mut changed = phases[0].clone();
+    changed.dutch_auction.as_mut().unwrap().floor_price = U128(to_yocto("1"));
+    assert!(!call!(runner.root, hall.set_phases(vec![changed])).is_ok());
+    call!(runner.root, hall.set_phases(vec![])).assert_success();
+
+    // a removed auction is over, buyers settle at the lowest price paid
+    let rebate: U128 =
+        view!(hall.rebate_of("AUCTION".to_string(), runner.alice.account_id())).unwrap_json();
+    assert_eq!(rebate.0, 0);
+    call!(runner.bob, hall.claim_rebate("AUCTION".to_string(), runner.alice.account_id()))
+        .assert_success();
+    assert_eq!(runner.pending_proceeds(&runner.root), to_yocto("10"));
+}
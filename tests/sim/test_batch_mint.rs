@@ -0,0 +1,89 @@
+use crate::*;
+use halloffame::mint_deposit;
+use near_sdk::Gas;
+use nft::{MintedTokens, MAX_MINT_BATCH};
+
+const MAX_GAS: Gas = Gas(300_000_000_000_000);
+
+fn mints(runner: &Runner, amount: u32) -> u64 {
+    let nft = &runner.nft;
+    let tx = call!(
+        runner.root,
+        nft.nft_mints(runner.alice.account_id(), amount),
+        deposit = to_yocto("5"),
+        gas = MAX_GAS.0
+    );
+    tx.assert_success();
+    let minted: MintedTokens = tx.unwrap_json();
+    assert_eq!(minted.tokens.len() as u32, amount);
+    assert_eq!(tx.logs().iter().filter(|log| log.contains("\"nft_mint\"")).count(), 1);
+    println!(
+        "nft_mints({}): {} gas, {} per token",
+        amount,
+        tx.gas_burnt().0,
+        tx.gas_burnt().0 / amount as u64
+    );
+    tx.gas_burnt().0
+}
+
+#[test]
+fn batch_mint_gas_benchmark() {
+    let runner = Runner::default();
+    // what `sacrifice` forwards to `nft_mints` when called with 300 TGas
+    let forwarded = MAX_GAS.0 - GAS_FOR_SACRIFICE.0 - GAS_FOR_RESOLVE_TRANSFER.0;
+    for amount in [1, 10, 20, MAX_MINT_BATCH] {
+        let gas = mints(&runner, amount);
+        assert!(gas <= GAS_FOR_NFT_MINTS.0 + GAS_FOR_NFT_MINT_CALL.0 * amount as u64);
+        assert!(gas <= forwarded);
+    }
+    assert_eq!(runner.nft_total_supply(), 71);
+}
+
+#[test]
+fn batch_mint_max_size() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    let tx = call!(
+        runner.root,
+        nft.nft_mints(runner.alice.account_id(), MAX_MINT_BATCH + 1),
+        deposit = to_yocto("5")
+    );
+    assert!(!tx.is_ok());
+    assert_eq!(runner.nft_total_supply(), 0);
+}
+
+#[test]
+fn batch_mint_single_event() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    let tx = call!(runner.root, nft.nft_mints(runner.bob.account_id(), 3), deposit = to_yocto("1"));
+    let log = tx.logs().iter().find(|log| log.contains("\"nft_mint\"")).unwrap().clone();
+    assert!(log.contains("[\"1\",\"2\",\"3\"]"));
+    let tokens: Vec<Token> =
+        view!(nft.nft_tokens_for_owner(runner.bob.account_id(), None, None)).unwrap_json();
+    assert_eq!(tokens.len(), 3);
+}
+
+#[test]
+fn mint_storage_cost_covers_actual() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    let longest = runner.root.create_user("a".repeat(64).parse().unwrap(), to_yocto("10"));
+    for (account, amount) in
+        [(&runner.alice, 1), (&runner.alice, 20), (&runner.bob, MAX_MINT_BATCH), (&longest, 1)]
+    {
+        let estimate: U128 =
+            view!(nft.mint_storage_cost(account.account_id(), amount)).unwrap_json();
+        assert!(estimate.0 <= mint_deposit(amount));
+        let tx = call!(
+            runner.root,
+            nft.nft_mints(account.account_id(), amount),
+            deposit = estimate.0,
+            gas = GAS_FOR_NFT_MINTS.0 + GAS_FOR_NFT_MINT_CALL.0 * amount as u64
+        );
+        tx.assert_success();
+        let minted: MintedTokens = tx.unwrap_json();
+        assert_eq!(minted.tokens.len() as u32, amount);
+        assert!(minted.storage_cost.0 <= estimate.0);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::*;
+use nft::Delegation;
+
+#[test]
+fn delegation_all_and_specific() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(2);
+
+    call!(runner.root, nft.delegate(runner.alice.account_id(), None), deposit = to_yocto("0.01"))
+        .assert_success();
+    call!(
+        runner.root,
+        nft.delegate(runner.bob.account_id(), Some(vec!["2".to_string()])),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+
+    let is_delegate = |delegate: &UserAccount, token_id: Option<&str>| -> bool {
+        view!(nft.is_delegate(
+            runner.root.account_id(),
+            delegate.account_id(),
+            token_id.map(|id| id.to_string())
+        ))
+        .unwrap_json()
+    };
+    assert!(is_delegate(&runner.alice, None));
+    assert!(is_delegate(&runner.alice, Some("1")));
+    assert!(!is_delegate(&runner.bob, None));
+    assert!(!is_delegate(&runner.bob, Some("1")));
+    assert!(is_delegate(&runner.bob, Some("2")));
+
+    let delegations: Vec<Delegation> =
+        view!(nft.delegations_for(runner.bob.account_id(), None, None)).unwrap_json();
+    assert_eq!(delegations.len(), 1);
+    assert_eq!(delegations[0].holder_id, runner.root.account_id());
+
+    call!(runner.eva, nft.delegate(runner.bob.account_id(), None), deposit = to_yocto("0.01"))
+        .assert_success();
+    let page: Vec<Delegation> =
+        view!(nft.delegations_for(runner.bob.account_id(), Some(U128(1)), Some(1))).unwrap_json();
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].holder_id, runner.eva.account_id());
+    assert!(view!(nft.delegations_for(runner.bob.account_id(), Some(U128(3)), None)).is_err());
+
+    runner.transfer(&runner.root, &runner.eva, "2");
+    assert!(!is_delegate(&runner.bob, Some("2")));
+
+    call!(runner.root, nft.revoke_delegation(runner.alice.account_id()), deposit = 1)
+        .assert_success();
+    assert!(!is_delegate(&runner.alice, Some("1")));
+}
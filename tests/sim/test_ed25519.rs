@@ -0,0 +1,74 @@
+use crate::*;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+use halloffame::KeyType;
+use near_sdk_sim::to_yocto;
+use std::convert::TryFrom;
+
+pub fn keypair() -> Keypair {
+    let secret = SecretKey::from_bytes(&[7; 32]).unwrap();
+    let public = PublicKey::from(&secret);
+    Keypair { secret, public }
+}
+
+/// `ed25519:` base58 form of the key, as NEAR tooling prints it.
+pub fn near_public_key(keypair: &Keypair) -> String {
+    let mut data = vec![0];
+    data.extend_from_slice(keypair.public.as_bytes());
+    String::from(&near_sdk::PublicKey::try_from(data).unwrap())
+}
+
+fn set_signer_pk(runner: &Runner, signer_pk: String) -> bool {
+    let hall = &runner.hall;
+    call!(
+        runner.root,
+        hall.sudo_config(ConfigUpdate { signer_pk: Some(signer_pk), ..Default::default() })
+    )
+    .is_ok()
+}
+
+fn sacrifice_with(runner: &Runner, permit: AllowlistPermit, signature: String) -> bool {
+    let hall = &runner.hall;
+    call!(
+        runner.alice,
+        hall.sacrifice(1, Some(permit), Some(signature)),
+        to_yocto("18"),
+        300_000_000_000_000
+    )
+    .is_ok()
+}
+
+#[test]
+fn ed25519_signer() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::InPrivate);
+    let keypair = keypair();
+    assert!(set_signer_pk(&runner, near_public_key(&keypair)));
+    let hall = &runner.hall;
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.signer_key_type, KeyType::Ed25519);
+
+    // secp256k1 signatures are no longer accepted
+    let permit = runner.permit(2);
+    assert!(!sacrifice_with(&runner, permit.clone(), runner.sign(&runner.alice, &permit)));
+
+    let payload = permit.payload(&runner.alice.account_id());
+    let signature = hex::encode(keypair.sign(payload.as_bytes()).to_bytes());
+    assert!(sacrifice_with(&runner, permit, signature));
+    assert_eq!(runner.nft_total_supply(), 1);
+
+    assert!(set_signer_pk(&runner, runner.signer_pk()));
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.signer_key_type, KeyType::Secp256k1);
+    assert!(runner.personal_sacrifice(to_yocto("18"), 1));
+}
+
+#[test]
+fn ed25519_invalid_key_rejected() {
+    let runner = Runner::default();
+    assert!(!set_signer_pk(&runner, "ed25519:not-base58".to_string()));
+    assert!(!set_signer_pk(&runner, "ed25519:1111".to_string()));
+    assert!(!set_signer_pk(&runner, "00".to_string()));
+    let hall = &runner.hall;
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.signer_pk, Some(runner.signer_pk()));
+}
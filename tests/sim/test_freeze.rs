@@ -0,0 +1,48 @@
+use crate::*;
+use nft::FrozenToken;
+
+#[test]
+fn freeze_blocks_all_transfer_paths() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(2);
+
+    assert!(!call!(runner.alice, nft.nft_freeze("1".to_string(), "stolen".to_string())).is_ok());
+    call!(runner.root, nft.nft_freeze("1".to_string(), "stolen".to_string())).assert_success();
+    let frozen: Vec<FrozenToken> = view!(nft.nft_frozen_tokens(None, None)).unwrap_json();
+    assert_eq!(frozen.len(), 1);
+    assert_eq!(frozen[0].reason, "stolen");
+
+    assert!(!call!(
+        runner.root,
+        nft.nft_transfer(runner.alice.account_id(), "1".to_string(), None, None),
+        deposit = 1
+    )
+    .is_ok());
+    assert!(!call!(
+        runner.root,
+        nft.nft_transfer_payout(
+            runner.alice.account_id(),
+            "1".to_string(),
+            None,
+            None,
+            U128(to_yocto("1")),
+            None
+        ),
+        deposit = 1
+    )
+    .is_ok());
+    assert!(!call!(
+        runner.root,
+        nft.list("1".to_string(), U128(to_yocto("1"))),
+        deposit = to_yocto("0.01")
+    )
+    .is_ok());
+    // other tokens are unaffected
+    runner.transfer(&runner.root, &runner.alice, "2");
+
+    call!(runner.root, nft.nft_unfreeze("1".to_string())).assert_success();
+    let frozen: Option<FrozenToken> = view!(nft.nft_frozen("1".to_string())).unwrap_json();
+    assert!(frozen.is_none());
+    runner.transfer(&runner.root, &runner.alice, "1");
+}
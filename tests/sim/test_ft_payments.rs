@@ -0,0 +1,103 @@
+use crate::*;
+use halloffame::SacrificeMsg;
+use near_sdk::serde_json;
+use near_sdk_sim::to_yocto;
+
+fn msg(amount: u32) -> String {
+    serde_json::to_string(&SacrificeMsg { amount, permit: None, signature: None }).unwrap()
+}
+
+fn accept_usdc(runner: &Runner, price: u128) -> UserAccount {
+    let usdc = runner.root.create_user("usdc".parse().unwrap(), to_yocto("10"));
+    let hall = &runner.hall;
+    call!(runner.root, hall.set_ft_price(usdc.account_id(), Some(U128(price)))).assert_success();
+    call!(runner.alice, hall.deposit_mint_storage(None), deposit = to_yocto("1")).assert_success();
+    usdc
+}
+
+fn mint_storage_balance(runner: &Runner) -> u128 {
+    let hall = &runner.hall;
+    let balance: U128 =
+        view!(hall.mint_storage_balance_of(runner.alice.account_id())).unwrap_json();
+    balance.0
+}
+
+#[test]
+fn ft_payment_refunds_unused() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::AfterPrivate);
+    let usdc = accept_usdc(&runner, 10);
+    let hall = &runner.hall;
+    let prices: Vec<(AccountId, U128)> = view!(hall.ft_prices()).unwrap_json();
+    assert_eq!(prices, vec![(usdc.account_id(), U128(10))]);
+
+    let tx = call!(usdc, hall.ft_on_transfer(runner.alice.account_id(), U128(25), msg(2)));
+    let unused: U128 = tx.unwrap_json();
+    assert_eq!(unused.0, 5);
+    assert_eq!(runner.nft_total_supply(), 2);
+    // storage of the passes is paid from the prepaid balance, the rest stays there
+    let balance = mint_storage_balance(&runner);
+    assert!(balance > 0 && balance < to_yocto("1"));
+    call!(runner.alice, hall.withdraw_mint_storage(), deposit = 1).assert_success();
+    assert_eq!(mint_storage_balance(&runner), 0);
+    runner.assert_spend_about(&runner.alice, 0);
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.public_sold, 2);
+
+    // the wallet cap applies to token payments as well
+    call!(runner.alice, hall.deposit_mint_storage(None), deposit = to_yocto("1")).assert_success();
+    let tx = call!(usdc, hall.ft_on_transfer(runner.alice.account_id(), U128(10), msg(1)));
+    assert!(!tx.is_ok());
+}
+
+#[test]
+fn ft_payment_requires_storage_prepaid() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::AfterPrivate);
+    let usdc = accept_usdc(&runner, 10);
+    let hall = &runner.hall;
+    call!(runner.alice, hall.withdraw_mint_storage(), deposit = 1).assert_success();
+
+    let tx = call!(usdc, hall.ft_on_transfer(runner.alice.account_id(), U128(20), msg(2)));
+    assert!(!tx.is_ok());
+    let tx = call!(usdc, hall.ft_on_transfer(runner.alice.account_id(), U128(0), msg(0)));
+    assert!(!tx.is_ok());
+    assert_eq!(runner.nft_total_supply(), 0);
+}
+
+#[test]
+fn ft_payment_rejected() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::AfterPrivate);
+    let usdc = accept_usdc(&runner, 10);
+    let hall = &runner.hall;
+
+    let tx = call!(usdc, hall.ft_on_transfer(runner.alice.account_id(), U128(15), msg(2)));
+    assert!(!tx.is_ok());
+    let tx = call!(runner.bob, hall.ft_on_transfer(runner.alice.account_id(), U128(20), msg(2)));
+    assert!(!tx.is_ok());
+    let tx = call!(usdc, hall.ft_on_transfer(runner.alice.account_id(), U128(20), "2".into()));
+    assert!(!tx.is_ok());
+
+    call!(runner.root, hall.set_ft_price(usdc.account_id(), None)).assert_success();
+    let tx = call!(usdc, hall.ft_on_transfer(runner.alice.account_id(), U128(20), msg(2)));
+    assert!(!tx.is_ok());
+    assert_eq!(runner.nft_total_supply(), 0);
+}
+
+#[test]
+fn ft_payment_refunded_when_mint_fails() {
+    let mut runner = Runner::new(1);
+    runner.time_travel_to(MomentInTime::AfterPrivate);
+    let usdc = accept_usdc(&runner, 10);
+    let hall = &runner.hall;
+
+    let tx = call!(usdc, hall.ft_on_transfer(runner.alice.account_id(), U128(20), msg(2)));
+    let unused: U128 = tx.unwrap_json();
+    assert_eq!(unused.0, 20);
+    assert_eq!(runner.nft_total_supply(), 0);
+    // only the sale counters of the buyer stay paid from the balance
+    assert!(mint_storage_balance(&runner) > to_yocto("0.99"));
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.total_sold, 0);
+}
@@ -0,0 +1,89 @@
+use crate::*;
+use near_contract_standards::non_fungible_token::metadata::TokenMetadata;
+use nft::FusionRecipe;
+
+pub fn gold_recipe() -> FusionRecipe {
+    FusionRecipe {
+        input_tier: 0,
+        input_count: 2,
+        output_tier: 1,
+        metadata: TokenMetadata {
+            title: Some("Exverse Gold Pass".to_string()),
+            description: None,
+            media: None,
+            media_hash: None,
+            copies: Some(1),
+            issued_at: None,
+            expires_at: None,
+            starts_at: None,
+            updated_at: None,
+            extra: None,
+            reference: Some("gold/{token_id}".to_string()),
+            reference_hash: None,
+        },
+    }
+}
+
+#[test]
+fn fusion_ok() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    call!(runner.root, nft.set_fusion_recipe("gold".to_string(), gold_recipe())).assert_success();
+    runner.take_out(3);
+
+    let token: Token = call!(
+        runner.root,
+        nft.nft_fuse("gold".to_string(), vec!["1".to_string(), "2".to_string()]),
+        deposit = to_yocto("0.1")
+    )
+    .unwrap_json();
+    assert_eq!(token.token_id, "F1");
+    assert_eq!(token.metadata.unwrap().reference, Some("gold/F1".to_string()));
+    assert_eq!(runner.nft_total_supply(), 2);
+    let tier: u8 = view!(nft.nft_tier("F1".to_string())).unwrap_json();
+    assert_eq!(tier, 1);
+}
+
+#[test]
+fn fusion_checks_inputs() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    call!(runner.root, nft.set_fusion_recipe("gold".to_string(), gold_recipe())).assert_success();
+    runner.take_out(3);
+    runner.transfer(&runner.root, &runner.alice, "3");
+
+    let fuse = |account: &UserAccount, token_ids: Vec<&str>| {
+        call!(
+            account,
+            nft.nft_fuse(
+                "gold".to_string(),
+                token_ids.into_iter().map(|id| id.to_string()).collect()
+            ),
+            deposit = to_yocto("0.1")
+        )
+        .is_ok()
+    };
+    assert!(!fuse(&runner.alice, vec!["1", "2"]));
+    assert!(!fuse(&runner.root, vec!["1", "3"]));
+    assert!(!fuse(&runner.root, vec!["1", "1"]));
+    assert!(!fuse(&runner.root, vec!["1"]));
+    assert_eq!(runner.nft_total_supply(), 3);
+}
+
+#[test]
+fn fusion_refunds_storage() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    call!(runner.root, nft.set_fusion_recipe("gold".to_string(), gold_recipe())).assert_success();
+    runner.take_out(2);
+
+    let balance = runner.root.account().unwrap().amount;
+    call!(
+        runner.root,
+        nft.nft_fuse("gold".to_string(), vec!["1".to_string(), "2".to_string()]),
+        deposit = to_yocto("1")
+    )
+    .assert_success();
+    // two burnt passes free more storage than the fused one takes
+    assert!(balance - runner.root.account().unwrap().amount < to_yocto("0.05"));
+}
@@ -0,0 +1,65 @@
+use crate::*;
+use near_sdk::json_types::Base58CryptoHash;
+use nft::HolderSnapshot;
+
+#[test]
+fn holders_follow_mints_and_transfers() {
+    let runner = Runner::default();
+    assert_eq!(runner.nft_holders_count(), 0);
+
+    runner.take_out(2);
+    assert_eq!(runner.nft_holders_count(), 1);
+
+    runner.transfer(&runner.root, &runner.alice, "1");
+    assert_eq!(runner.nft_holders_count(), 2);
+    let nft = &runner.nft;
+    let holders: Vec<(AccountId, U128)> = view!(nft.nft_holders(None, None)).unwrap_json();
+    assert!(holders.contains(&(runner.root.account_id(), U128(1))));
+    assert!(holders.contains(&(runner.alice.account_id(), U128(1))));
+
+    runner.transfer(&runner.alice, &runner.root, "1");
+    assert_eq!(runner.nft_holders_count(), 1);
+}
+
+#[test]
+fn holders_snapshot() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(2);
+    let root: Base58CryptoHash = [1; 32].into();
+    let height = runner.root.borrow_runtime().current_block().block_height;
+
+    assert!(!call!(runner.alice, nft.snapshot_holders(height, 1, root.clone())).is_ok());
+    assert!(!call!(runner.root, nft.snapshot_holders(height + 100, 1, root.clone())).is_ok());
+    let first: HolderSnapshot =
+        call!(runner.root, nft.snapshot_holders(height, 1, root.clone())).unwrap_json();
+    assert_eq!(first.holders, 1);
+    assert_eq!(first.block_height, height);
+    assert!(!call!(runner.root, nft.snapshot_holders(height, 1, root.clone())).is_ok());
+
+    let later = runner.root.borrow_runtime().current_block().block_height;
+    let second: HolderSnapshot =
+        call!(runner.root, nft.snapshot_holders(later, 2, [2; 32].into())).unwrap_json();
+    assert_ne!(String::from(&first.root), String::from(&second.root));
+
+    let stored: Option<HolderSnapshot> = view!(nft.holder_snapshot(0)).unwrap_json();
+    assert_eq!(stored.unwrap().block_height, first.block_height);
+    let count: u64 = view!(nft.holder_snapshots_count()).unwrap_json();
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn holders_backfill() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(3);
+    runner.transfer(&runner.root, &runner.alice, "2");
+
+    assert!(!call!(runner.alice, nft.backfill_holders(None, None)).is_ok());
+    assert!(!call!(runner.root, nft.backfill_holders(Some(U128(4)), None)).is_ok());
+    let holders: u64 = call!(runner.root, nft.backfill_holders(None, Some(2))).unwrap_json();
+    assert_eq!(holders, 2);
+    let holders: u64 =
+        call!(runner.root, nft.backfill_holders(Some(U128(2)), Some(2))).unwrap_json();
+    assert_eq!(holders, 2);
+}
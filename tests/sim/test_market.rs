@@ -0,0 +1,40 @@
+use crate::*;
+use nft::Listing;
+
+#[test]
+fn market_buy() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(1);
+    runner.transfer(&runner.root, &runner.alice, "1");
+
+    call!(runner.alice, nft.list("1".to_string(), U128(to_yocto("5"))), deposit = to_yocto("0.01"))
+        .assert_success();
+    let listings: Vec<Listing> = view!(nft.listings(None, None)).unwrap_json();
+    assert_eq!(listings.len(), 1);
+
+    assert!(!call!(runner.bob, nft.buy("1".to_string()), deposit = to_yocto("4")).is_ok());
+    let balance = runner.alice.account().unwrap().amount;
+    call!(runner.bob, nft.buy("1".to_string()), deposit = to_yocto("6")).assert_success();
+    assert!(runner.alice.account().unwrap().amount - balance >= to_yocto("5"));
+    runner.assert_spend_about(&runner.bob, to_yocto("5"));
+
+    let listing: Option<Listing> = view!(nft.listing("1".to_string())).unwrap_json();
+    assert!(listing.is_none());
+    let token: Option<Token> = view!(nft.nft_token("1".to_string())).unwrap_json();
+    assert_eq!(token.unwrap().owner_id, runner.bob.account_id());
+}
+
+#[test]
+fn market_transfer_drops_listing() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(1);
+
+    call!(runner.root, nft.list("1".to_string(), U128(to_yocto("5"))), deposit = to_yocto("0.01"))
+        .assert_success();
+    runner.transfer(&runner.root, &runner.alice, "1");
+    let count: u64 = view!(nft.listings_count()).unwrap_json();
+    assert_eq!(count, 0);
+    assert!(!call!(runner.bob, nft.buy("1".to_string()), deposit = to_yocto("5")).is_ok());
+}
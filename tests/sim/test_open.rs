@@ -22,11 +22,10 @@ fn open_ok_many() {
 fn open_ok() {
     let mut runner = Runner::default();
     runner.time_travel_to(MomentInTime::AfterPrivate);
-    let initial_balance = runner.root.account().unwrap().amount;
     assert!(runner.sacrifice(to_yocto("17.5") * 2 + to_yocto("0.05") * 2, 2));
     assert_eq!(runner.nft_total_supply(), 2);
     runner.assert_spend_about(&runner.alice, to_yocto("17.5") * 2);
-    assert_eq!(runner.root.account().unwrap().amount - initial_balance, to_yocto("17.5") * 2);
+    assert_eq!(runner.pending_proceeds(&runner.root), to_yocto("17.5") * 2);
 }
 
 #[test]
@@ -0,0 +1,73 @@
+use crate::*;
+use near_sdk_sim::to_yocto;
+
+#[test]
+fn permit_ok() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::InPrivate);
+    let permit = runner.permit(1);
+    assert_eq!(permit.phase, "PRIVATE");
+    assert!(runner.personal_sacrifice_permit(to_yocto("18"), 1, permit));
+    assert_eq!(runner.nft_total_supply(), 1);
+}
+
+#[test]
+fn permit_foreign_rejected() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::InPrivate);
+
+    let mut permit = runner.permit(1);
+    permit.contract_id = runner.nft.account_id();
+    assert!(!runner.personal_sacrifice_permit(to_yocto("18"), 1, permit));
+    let mut permit = runner.permit(1);
+    permit.phase = "OPEN".to_string();
+    assert!(!runner.personal_sacrifice_permit(to_yocto("18"), 1, permit));
+    // priced in a token, paid in NEAR
+    let mut permit = runner.permit(1);
+    permit.token_id = Some(runner.eva.account_id());
+    assert!(!runner.personal_sacrifice_permit(to_yocto("18"), 1, permit));
+    // signed for bob, used by alice
+    let permit = runner.permit(1);
+    let signature = runner.sign(&runner.bob, &permit);
+    let hall = &runner.hall;
+    assert!(!call!(
+        runner.alice,
+        hall.sacrifice(1, Some(permit), Some(signature)),
+        to_yocto("18"),
+        300_000_000_000_000
+    )
+    .is_ok());
+    assert_eq!(runner.nft_total_supply(), 0);
+}
+
+#[test]
+fn permit_expired() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::InPrivate);
+    let mut permit = runner.permit(1);
+    permit.expires_at = runner.now() / 1_000_000_000 + 5;
+    runner.wait(10);
+    assert!(!runner.personal_sacrifice_permit(to_yocto("18"), 1, permit));
+    assert_eq!(runner.nft_total_supply(), 0);
+}
+
+#[test]
+fn permit_price_ceiling() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::InPrivate);
+    let mut permit = runner.permit(2);
+    permit.price = U128(to_yocto("10"));
+    assert!(!runner.personal_sacrifice_permit(to_yocto("40"), 1, permit.clone()));
+    runner.change_price(to_yocto("10"));
+    assert!(runner.personal_sacrifice_permit(to_yocto("40"), 2, permit));
+    runner.assert_spend_about(&runner.alice, to_yocto("20"));
+}
+
+#[test]
+fn permit_phase_name_without_separator() {
+    let runner = Runner::default();
+    let hall = &runner.hall;
+    let now = runner.now() / 1_000_000_000;
+    let ambiguous = crate::test_phases::phase("EARLY:1", now, 0, "1", true);
+    assert!(!call!(runner.root, hall.set_phases(vec![ambiguous])).is_ok());
+}
@@ -0,0 +1,101 @@
+use crate::*;
+use halloffame::Phase;
+
+pub fn phase(name: &str, start: u64, end: u64, price: &str, allowlist: bool) -> Phase {
+    Phase {
+        name: name.to_string(),
+        start,
+        end,
+        price: Some(U128(to_yocto(price))),
+        wallet_cap: None,
+        transaction_cap: None,
+        allowlist,
+        supply_cap: None,
+        dutch_auction: None,
+        public_limits: false,
+    }
+}
+
+#[test]
+fn phases_resolve_current() {
+    let runner = Runner::default();
+    let hall = &runner.hall;
+    let now = runner.now() / 1_000_000_000;
+
+    let mut early = phase("EARLY", now - 10, now + 100, "1", true);
+    early.wallet_cap = Some(1);
+    let public = phase("PUBLIC", now + 100, 0, "2", false);
+    let unordered = vec![public.clone(), early.clone()];
+    assert!(!call!(runner.root, hall.set_phases(unordered)).is_ok());
+    assert!(!call!(runner.alice, hall.set_phases(vec![early.clone()])).is_ok());
+    call!(runner.root, hall.set_phases(vec![early, public])).assert_success();
+
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.stage, "EARLY");
+    assert_eq!(config.active_phase.unwrap().name, "EARLY");
+    assert_eq!(config.upcoming_phases.len(), 1);
+
+    assert!(!runner.sacrifice(to_yocto("5"), 1));
+    assert!(!runner.personal_sacrifice_force(to_yocto("5"), 2));
+    assert!(runner.personal_sacrifice_force(to_yocto("5"), 1));
+    runner.assert_spend_about(&runner.alice, to_yocto("1"));
+    let sold: u32 = view!(hall.phase_sold("EARLY".to_string(), None)).unwrap_json();
+    assert_eq!(sold, 1);
+}
+
+#[test]
+fn phases_supply_cap() {
+    let runner = Runner::default();
+    let hall = &runner.hall;
+    let now = runner.now() / 1_000_000_000;
+
+    let mut public = phase("PUBLIC", now - 10, 0, "2", false);
+    public.supply_cap = Some(3);
+    call!(runner.root, hall.set_phases(vec![public])).assert_success();
+
+    assert!(runner.sacrifice(to_yocto("7"), 3));
+    runner.assert_spend_about(&runner.alice, to_yocto("6"));
+    assert!(!runner.sacrifice(to_yocto("7"), 1));
+    assert_eq!(runner.nft_total_supply(), 3);
+}
+
+#[test]
+fn phases_failed_mint_releases_supply() {
+    let runner = Runner::new(2);
+    let hall = &runner.hall;
+    let now = runner.now() / 1_000_000_000;
+
+    let mut public = phase("PUBLIC", now - 10, 0, "2", false);
+    public.supply_cap = Some(3);
+    call!(runner.root, hall.set_phases(vec![public])).assert_success();
+
+    // reserved at purchase, given back once the nft contract refuses the mint
+    assert!(!runner.sacrifice(to_yocto("7"), 3));
+    let sold: u32 = view!(hall.phase_sold("PUBLIC".to_string(), None)).unwrap_json();
+    assert_eq!(sold, 0);
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.total_sold, 0);
+
+    assert!(runner.sacrifice(to_yocto("5"), 2));
+    let sold: u32 = view!(hall.phase_sold("PUBLIC".to_string(), None)).unwrap_json();
+    assert_eq!(sold, 2);
+}
+
+#[test]
+fn phases_public_limits_opt_in() {
+    let runner = Runner::default();
+    let hall = &runner.hall;
+    let now = runner.now() / 1_000_000_000;
+
+    // public_wallet_cap defaults to 2 but only applies to phases that opt in
+    let public = phase("PUBLIC", now - 10, 0, "2", false);
+    call!(runner.root, hall.set_phases(vec![public])).assert_success();
+    assert!(runner.sacrifice(to_yocto("7"), 3));
+
+    let mut late = phase("LATE", now - 10, 0, "2", false);
+    late.public_limits = true;
+    call!(runner.root, hall.set_phases(vec![late])).assert_success();
+    assert!(!runner.sacrifice(to_yocto("7"), 3));
+    assert!(runner.sacrifice(to_yocto("5"), 2));
+    assert!(!runner.sacrifice(to_yocto("3"), 1));
+}
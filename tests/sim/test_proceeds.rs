@@ -0,0 +1,46 @@
+use crate::*;
+use near_sdk_sim::to_yocto;
+
+#[test]
+fn proceeds_withdraw() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::AfterPrivate);
+    runner.change_price(to_yocto("10"));
+    assert!(runner.sacrifice(to_yocto("21"), 2));
+    assert_eq!(runner.pending_proceeds(&runner.root), to_yocto("20"));
+
+    let hall = &runner.hall;
+    assert!(!call!(runner.root, hall.withdraw(None)).is_ok());
+    let balance = runner.root.account().unwrap().amount;
+    call!(runner.root, hall.withdraw(None), deposit = 1).assert_success();
+    assert!(runner.root.account().unwrap().amount - balance > to_yocto("19.9"));
+    let proceeds: Vec<Proceeds> = view!(hall.proceeds_of(runner.root.account_id())).unwrap_json();
+    assert_eq!(
+        proceeds,
+        vec![Proceeds { token_id: None, pending: U128(0), withdrawn: U128(to_yocto("20")) }]
+    );
+
+    assert!(!call!(runner.root, hall.withdraw(None), deposit = 1).is_ok());
+    assert!(!call!(runner.alice, hall.withdraw(None), deposit = 1).is_ok());
+}
+
+#[test]
+fn proceeds_kept_when_withdrawal_fails() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::AfterPrivate);
+    let hall = &runner.hall;
+    let usdc = runner.root.create_user("usdc".parse().unwrap(), to_yocto("10"));
+    call!(runner.root, hall.set_ft_price(usdc.account_id(), Some(U128(10)))).assert_success();
+    let msg = r#"{"amount":1,"permit":null,"signature":null}"#.to_string();
+    call!(usdc, hall.ft_on_transfer(runner.alice.account_id(), U128(10), msg)).assert_success();
+
+    // `usdc` has no contract to transfer with, the tokens stay withdrawable
+    let tx = call!(runner.root, hall.withdraw(Some(usdc.account_id())), deposit = 1);
+    let withdrawn: bool = tx.unwrap_json();
+    assert!(!withdrawn);
+    let proceeds: Vec<Proceeds> = view!(hall.proceeds_of(runner.root.account_id())).unwrap_json();
+    assert_eq!(
+        proceeds,
+        vec![Proceeds { token_id: Some(usdc.account_id()), pending: U128(10), withdrawn: U128(0) }]
+    );
+}
@@ -0,0 +1,39 @@
+use crate::*;
+use near_sdk_sim::to_yocto;
+
+#[test]
+fn public_limits_default() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::AfterPrivate);
+    let hall = &runner.hall;
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.public_wallet_cap, Some(2));
+    assert_eq!(config.transaction_cap, None);
+    assert!(!runner.sacrifice(to_yocto("60"), 3));
+}
+
+#[test]
+fn public_limits_configured() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::AfterPrivate);
+    runner.set_public_limits(4, 2, 3);
+    let hall = &runner.hall;
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.public_wallet_cap, Some(4));
+    assert_eq!(config.transaction_cap, Some(2));
+    assert_eq!(config.public_allocation, Some(3));
+
+    assert!(!runner.sacrifice(to_yocto("60"), 3));
+    assert!(runner.sacrifice(to_yocto("40"), 2));
+    // wallet cap allows two more, the public allocation only one
+    assert!(!runner.sacrifice(to_yocto("40"), 2));
+    assert!(runner.sacrifice(to_yocto("20"), 1));
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.public_sold, 3);
+
+    runner.set_public_limits(0, 0, 0);
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.public_wallet_cap, None);
+    assert!(runner.sacrifice(to_yocto("60"), 3));
+    assert_eq!(runner.nft_total_supply(), 6);
+}
@@ -0,0 +1,122 @@
+use crate::test_fusion::gold_recipe;
+use crate::*;
+use nft::{FrozenToken, Listing, RecoveryConfig, RecoveryRequest};
+
+#[test]
+fn recovery_approved_after_waiting_period() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(1);
+    runner.transfer(&runner.root, &runner.bob, "1");
+    call!(runner.root, nft.extend_operators(vec![runner.eva.account_id()]), deposit = 1)
+        .assert_success();
+    call!(runner.root, nft.set_recovery_config(RecoveryConfig { threshold: 2, delay_sec: 5 }))
+        .assert_success();
+
+    call!(
+        runner.alice,
+        nft.file_recovery("1".to_string(), "sent to a wrong account".to_string()),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    // filing alone doesn't freeze the pass
+    let frozen: Option<FrozenToken> = view!(nft.nft_frozen("1".to_string())).unwrap_json();
+    assert!(frozen.is_none());
+
+    assert!(!call!(runner.bob, nft.approve_recovery("1".to_string())).is_ok());
+    call!(runner.root, nft.approve_recovery("1".to_string())).assert_success();
+    // the holder can't move the pass once the request is approved
+    assert!(!call!(
+        runner.bob,
+        nft.nft_transfer(runner.eva.account_id(), "1".to_string(), None, None),
+        deposit = 1
+    )
+    .is_ok());
+    assert!(!call!(runner.root, nft.nft_unfreeze("1".to_string())).is_ok());
+    assert!(!call!(runner.root, nft.execute_recovery("1".to_string())).is_ok());
+    call!(runner.eva, nft.approve_recovery("1".to_string())).assert_success();
+
+    let request: Option<RecoveryRequest> =
+        view!(nft.recovery_request("1".to_string())).unwrap_json();
+    let request = request.unwrap();
+    assert_eq!(request.holder_id, runner.bob.account_id());
+    assert!(request.executable_at.unwrap().0 > runner.now());
+    assert!(!call!(runner.root, nft.execute_recovery("1".to_string())).is_ok());
+
+    runner.wait(10);
+    call!(runner.root, nft.execute_recovery("1".to_string())).assert_success();
+    let token: Token = view!(nft.nft_token("1".to_string())).unwrap_json();
+    assert_eq!(token.owner_id, runner.alice.account_id());
+    runner.transfer(&runner.alice, &runner.bob, "1");
+}
+
+#[test]
+fn recovery_rejected_unfreezes() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(1);
+
+    call!(
+        runner.alice,
+        nft.file_recovery("1".to_string(), "mine".to_string()),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    assert!(!call!(runner.bob, nft.cancel_recovery("1".to_string()), deposit = 1).is_ok());
+    call!(runner.root, nft.approve_recovery("1".to_string())).assert_success();
+    call!(runner.root, nft.cancel_recovery("1".to_string()), deposit = 1).assert_success();
+
+    let requests: Vec<RecoveryRequest> = view!(nft.recovery_requests(None, None)).unwrap_json();
+    assert!(requests.is_empty());
+    runner.transfer(&runner.root, &runner.bob, "1");
+}
+
+#[test]
+fn recovery_filing_does_not_freeze() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(1);
+    runner.transfer(&runner.root, &runner.bob, "1");
+    call!(runner.bob, nft.list("1".to_string(), U128(to_yocto("1"))), deposit = to_yocto("0.01"))
+        .assert_success();
+
+    call!(
+        runner.alice,
+        nft.file_recovery("1".to_string(), "mine".to_string()),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    let listing: Option<Listing> = view!(nft.listing("1".to_string())).unwrap_json();
+    assert!(listing.is_some());
+    runner.transfer(&runner.bob, &runner.eva, "1");
+
+    call!(runner.root, nft.approve_recovery("1".to_string())).assert_success();
+    let request: Option<RecoveryRequest> =
+        view!(nft.recovery_request("1".to_string())).unwrap_json();
+    assert_eq!(request.unwrap().holder_id, runner.eva.account_id());
+}
+
+#[test]
+fn recovery_dropped_on_burn() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    call!(runner.root, nft.set_fusion_recipe("gold".to_string(), gold_recipe())).assert_success();
+    runner.take_out(2);
+    call!(
+        runner.alice,
+        nft.file_recovery("1".to_string(), "mine".to_string()),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+
+    call!(
+        runner.root,
+        nft.nft_fuse("gold".to_string(), vec!["1".to_string(), "2".to_string()]),
+        deposit = to_yocto("0.1")
+    )
+    .assert_success();
+    let request: Option<RecoveryRequest> =
+        view!(nft.recovery_request("1".to_string())).unwrap_json();
+    assert!(request.is_none());
+    assert!(!call!(runner.root, nft.approve_recovery("1".to_string())).is_ok());
+}
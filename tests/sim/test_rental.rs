@@ -0,0 +1,47 @@
+use crate::*;
+use near_sdk::json_types::U64;
+
+#[test]
+fn rental_user_expires() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(1);
+
+    let expires_at = runner.now() + 5 * 1_000_000_000;
+    assert!(!call!(
+        runner.alice,
+        nft.nft_set_user("1".to_string(), runner.alice.account_id(), U64(expires_at)),
+        deposit = to_yocto("0.01")
+    )
+    .is_ok());
+    call!(
+        runner.root,
+        nft.nft_set_user("1".to_string(), runner.alice.account_id(), U64(expires_at)),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    let user: Option<AccountId> = view!(nft.nft_user_of("1".to_string())).unwrap_json();
+    assert_eq!(user, Some(runner.alice.account_id()));
+
+    runner.wait(10);
+    let user: Option<AccountId> = view!(nft.nft_user_of("1".to_string())).unwrap_json();
+    assert!(user.is_none());
+}
+
+#[test]
+fn rental_user_cleared_on_transfer() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(1);
+
+    let expires_at = runner.now() + 3600 * 1_000_000_000;
+    call!(
+        runner.root,
+        nft.nft_set_user("1".to_string(), runner.alice.account_id(), U64(expires_at)),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    runner.transfer(&runner.root, &runner.bob, "1");
+    let user: Option<AccountId> = view!(nft.nft_user_of("1".to_string())).unwrap_json();
+    assert!(user.is_none());
+}
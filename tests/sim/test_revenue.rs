@@ -0,0 +1,81 @@
+use crate::*;
+use halloffame::Beneficiary;
+use near_sdk_sim::to_yocto;
+
+fn beneficiary(account: &UserAccount, bps: u16) -> Beneficiary {
+    Beneficiary { account_id: account.account_id(), bps }
+}
+
+#[test]
+fn revenue_defaults_to_treasury() {
+    let runner = Runner::default();
+    let hall = &runner.hall;
+    let split: Vec<Beneficiary> = view!(hall.revenue_split()).unwrap_json();
+    assert_eq!(split, vec![beneficiary(&runner.root, 10_000)]);
+
+    // without a split proceeds follow the treasury, not the owner
+    call!(
+        runner.root,
+        hall.sudo_config(ConfigUpdate {
+            treasury_id: Some(runner.eva.account_id()),
+            ..Default::default()
+        })
+    )
+    .assert_success();
+    call!(runner.root, hall.set_owner(runner.bob.account_id()), deposit = 1).assert_success();
+    let split: Vec<Beneficiary> = view!(hall.revenue_split()).unwrap_json();
+    assert_eq!(split, vec![beneficiary(&runner.eva, 10_000)]);
+}
+
+#[test]
+fn revenue_split_by_bps() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::AfterPrivate);
+    runner.change_price(to_yocto("10"));
+    let hall = &runner.hall;
+    let split = vec![
+        beneficiary(&runner.root, 5_000),
+        beneficiary(&runner.eva, 3_000),
+        beneficiary(&runner.bob, 2_000),
+    ];
+    call!(runner.root, hall.set_revenue_split(split.clone()), deposit = 1).assert_success();
+    let view_split: Vec<Beneficiary> = view!(hall.revenue_split()).unwrap_json();
+    assert_eq!(view_split, split);
+
+    let tx = call!(
+        runner.alice,
+        hall.sacrifice(2, None, None),
+        to_yocto("21"),
+        GAS_FOR_RESOLVE_TRANSFER.0
+            + GAS_FOR_SACRIFICE.0
+            + GAS_FOR_NFT_MINTS.0
+            + GAS_FOR_NFT_MINT_CALL.0 * 2
+    );
+    tx.assert_success();
+    assert_eq!(runner.pending_proceeds(&runner.root), to_yocto("10"));
+    assert_eq!(runner.pending_proceeds(&runner.eva), to_yocto("6"));
+    assert_eq!(runner.pending_proceeds(&runner.bob), to_yocto("4"));
+    let distributions = tx
+        .promise_results()
+        .into_iter()
+        .flatten()
+        .flat_map(|result| result.logs().clone())
+        .filter(|log| log.contains("\"distribution\""))
+        .count();
+    assert_eq!(distributions, 1);
+}
+
+#[test]
+fn revenue_split_validates() {
+    let runner = Runner::default();
+    let hall = &runner.hall;
+    let short = vec![beneficiary(&runner.root, 5_000), beneficiary(&runner.eva, 3_000)];
+    assert!(!call!(runner.root, hall.set_revenue_split(short), deposit = 1).is_ok());
+    let duplicate = vec![beneficiary(&runner.eva, 5_000), beneficiary(&runner.eva, 5_000)];
+    assert!(!call!(runner.root, hall.set_revenue_split(duplicate), deposit = 1).is_ok());
+    let zero = vec![beneficiary(&runner.eva, 10_000), beneficiary(&runner.bob, 0)];
+    assert!(!call!(runner.root, hall.set_revenue_split(zero), deposit = 1).is_ok());
+    let valid = vec![beneficiary(&runner.eva, 10_000)];
+    assert!(!call!(runner.alice, hall.set_revenue_split(valid.clone()), deposit = 1).is_ok());
+    assert!(!call!(runner.root, hall.set_revenue_split(valid), deposit = 0).is_ok());
+}
@@ -0,0 +1,147 @@
+use crate::*;
+use halloffame::{KeyType, SignerKey};
+use near_sdk_sim::to_yocto;
+
+fn secret_key(seed: u8) -> secp256k1::SecretKey {
+    secp256k1::SecretKey::parse(&[seed; 32]).unwrap()
+}
+
+fn public_key(sk: &secp256k1::SecretKey) -> String {
+    hex::encode(secp256k1::PublicKey::from_secret_key(sk).serialize_compressed())
+}
+
+fn sign_with(runner: &Runner, sk: &secp256k1::SecretKey, permit: &AllowlistPermit) -> String {
+    let hash = runner.hash(permit.payload(&runner.alice.account_id()));
+    let msg = secp256k1::Message::parse(&hash);
+    let (sig, _) = secp256k1::sign(&msg, sk);
+    hex::encode(sig.serialize())
+}
+
+fn sacrifice_with(runner: &Runner, sk: &secp256k1::SecretKey) -> bool {
+    let permit = runner.permit(5);
+    let signature = sign_with(runner, sk, &permit);
+    let hall = &runner.hall;
+    call!(
+        runner.alice,
+        hall.sacrifice(1, Some(permit), Some(signature)),
+        to_yocto("18"),
+        300_000_000_000_000
+    )
+    .is_ok()
+}
+
+fn add_key(runner: &Runner, sk: &secp256k1::SecretKey, label: &str, from: Option<u64>) -> bool {
+    let hall = &runner.hall;
+    call!(
+        runner.root,
+        hall.add_signer_key(public_key(sk), label.to_string(), from, None),
+        deposit = 1
+    )
+    .is_ok()
+}
+
+#[test]
+fn signer_keys_rotation() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::InPrivate);
+    let backend = secret_key(2);
+    assert!(add_key(&runner, &backend, "backend-2", None));
+    let hall = &runner.hall;
+    let keys: Vec<SignerKey> = view!(hall.signer_keys()).unwrap_json();
+    assert_eq!(keys.len(), 2);
+    assert_eq!(keys[0].label, "default");
+    assert_eq!(
+        keys[1],
+        SignerKey {
+            public_key: public_key(&backend),
+            key_type: KeyType::Secp256k1,
+            label: "backend-2".to_string(),
+            valid_from: 0,
+            valid_until: 0,
+        }
+    );
+
+    // both keys are accepted until the leaked one is revoked
+    assert!(sacrifice_with(&runner, &runner.keypair));
+    assert!(sacrifice_with(&runner, &backend));
+    call!(runner.root, hall.revoke_signer_key(runner.signer_pk()), deposit = 1).assert_success();
+    assert!(!sacrifice_with(&runner, &runner.keypair));
+    assert!(sacrifice_with(&runner, &backend));
+    assert_eq!(runner.nft_total_supply(), 3);
+
+    call!(runner.root, hall.revoke_signer_key(public_key(&backend)), deposit = 1).assert_success();
+    assert!(!sacrifice_with(&runner, &backend));
+    let keys: Vec<SignerKey> = view!(hall.signer_keys()).unwrap_json();
+    assert!(keys.is_empty());
+}
+
+#[test]
+fn signer_keys_validity_window() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::InPrivate);
+    let next = secret_key(3);
+    let now = runner.now() / 1_000_000_000;
+    assert!(add_key(&runner, &next, "next", Some(now + 10)));
+    assert!(!sacrifice_with(&runner, &next));
+    runner.wait(15);
+    assert!(sacrifice_with(&runner, &next));
+    assert_eq!(runner.nft_total_supply(), 1);
+}
+
+#[test]
+fn signer_keys_owner_only() {
+    let runner = Runner::default();
+    let hall = &runner.hall;
+    let backend = secret_key(2);
+    assert!(!call!(
+        runner.alice,
+        hall.add_signer_key(public_key(&backend), "backend".to_string(), None, None),
+        deposit = 1
+    )
+    .is_ok());
+    assert!(add_key(&runner, &backend, "backend", None));
+    assert!(!add_key(&runner, &backend, "backend", None));
+    assert!(!call!(runner.alice, hall.revoke_signer_key(public_key(&backend)), deposit = 1).is_ok());
+    assert!(!call!(runner.root, hall.revoke_signer_key(public_key(&secret_key(4))), deposit = 1)
+        .is_ok());
+    assert!(!call!(
+        runner.root,
+        hall.add_signer_key("00".to_string(), "broken".to_string(), None, None),
+        deposit = 1
+    )
+    .is_ok());
+}
+
+#[test]
+fn signer_keys_mixed_types() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::InPrivate);
+    let hall = &runner.hall;
+    // the ed25519 key is checked first, the secp256k1 signature must not make it panic
+    let ed25519_pk = crate::test_ed25519::near_public_key(&crate::test_ed25519::keypair());
+    call!(
+        runner.root,
+        hall.sudo_config(ConfigUpdate { signer_pk: Some(ed25519_pk), ..Default::default() })
+    )
+    .assert_success();
+    let backend = secret_key(2);
+    assert!(add_key(&runner, &backend, "backend-2", None));
+
+    // most secp256k1 signatures don't parse as ed25519, find one for the test
+    let (permit, signature) = (1..20)
+        .map(|amount| {
+            let permit = runner.permit(amount);
+            let signature = sign_with(&runner, &backend, &permit);
+            (permit, signature)
+        })
+        .find(|(_, signature)| hex::decode(signature).unwrap()[63] & 0xE0 != 0)
+        .unwrap();
+    call!(
+        runner.alice,
+        hall.sacrifice(1, Some(permit), Some(signature)),
+        to_yocto("18"),
+        300_000_000_000_000
+    )
+    .assert_success();
+    assert_eq!(runner.nft_total_supply(), 1);
+}
@@ -0,0 +1,47 @@
+use crate::*;
+use nft::StakerView;
+
+#[test]
+fn staking_accrues_points() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(2);
+
+    call!(
+        runner.root,
+        nft.stake(vec!["1".to_string(), "2".to_string()]),
+        deposit = to_yocto("0.01")
+    )
+    .assert_success();
+    runner.wait(10);
+    let staker: StakerView = view!(nft.staker(runner.root.account_id())).unwrap_json();
+    assert_eq!(staker.staked, 2);
+    assert!(staker.points.0 >= 20);
+
+    call!(runner.root, nft.unstake(vec!["2".to_string()]), deposit = 1).assert_success();
+    let staked: Vec<String> =
+        view!(nft.staked_tokens(runner.root.account_id(), None, None)).unwrap_json();
+    assert_eq!(staked, vec!["1".to_string()]);
+    let leaderboard: Vec<(AccountId, StakerView)> = view!(nft.stakers(None, None)).unwrap_json();
+    assert_eq!(leaderboard.len(), 1);
+}
+
+#[test]
+fn staked_token_is_locked() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(1);
+
+    call!(runner.root, nft.stake(vec!["1".to_string()]), deposit = to_yocto("0.01"))
+        .assert_success();
+    let tx = call!(
+        runner.root,
+        nft.nft_transfer(runner.alice.account_id(), "1".to_string(), None, None),
+        deposit = 1
+    );
+    assert!(!tx.is_ok());
+    assert!(!call!(runner.alice, nft.unstake(vec!["1".to_string()]), deposit = 1).is_ok());
+
+    call!(runner.root, nft.unstake(vec!["1".to_string()]), deposit = 1).assert_success();
+    runner.transfer(&runner.root, &runner.alice, "1");
+}
@@ -0,0 +1,61 @@
+use crate::*;
+use halloffame::PriceTier;
+use near_sdk_sim::to_yocto;
+
+#[test]
+fn tiers_priced_by_total_sold() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::AfterPrivate);
+    runner.set_public_limits(0, 0, 0);
+    let hall = &runner.hall;
+    let tiers = vec![
+        PriceTier { amount: 2, price: to_yocto("1").into() },
+        PriceTier { amount: 3, price: to_yocto("2").into() },
+    ];
+    call!(runner.root, hall.set_price_tiers(tiers)).assert_success();
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.tier_remaining, Some(2));
+
+    assert!(!runner.sacrifice(to_yocto("2"), 0));
+    assert!(runner.sacrifice(to_yocto("2"), 1));
+    // one pass left at 1 NEAR, the next two cost 2 NEAR each
+    assert!(!runner.sacrifice(to_yocto("4.9"), 3));
+    assert!(runner.sacrifice(to_yocto("6"), 3));
+    runner.assert_spend_about(&runner.alice, to_yocto("6"));
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.total_sold, 4);
+    assert_eq!(config.tier_remaining, Some(1));
+
+    assert!(!runner.sacrifice(to_yocto("10"), 2));
+    assert!(runner.sacrifice(to_yocto("10"), 1));
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.tier_remaining, None);
+    assert!(!runner.sacrifice(to_yocto("10"), 1));
+    assert_eq!(runner.nft_total_supply(), 5);
+}
+
+#[test]
+fn tiers_skipped_by_phase_price() {
+    let mut runner = Runner::default();
+    runner.time_travel_to(MomentInTime::AfterPrivate);
+    let hall = &runner.hall;
+    let tiers = vec![PriceTier { amount: 1, price: to_yocto("1").into() }];
+    call!(runner.root, hall.set_price_tiers(tiers)).assert_success();
+    let public = crate::test_phases::phase("PUBLIC", 0, 0, "3", false);
+    call!(runner.root, hall.set_phases(vec![public])).assert_success();
+
+    assert!(runner.sacrifice(to_yocto("6"), 2));
+    runner.assert_spend_about(&runner.alice, to_yocto("6"));
+    let config: Config = view!(hall.config()).unwrap_json();
+    assert_eq!(config.total_sold, 2);
+}
+
+#[test]
+fn set_price_tiers_validates() {
+    let runner = Runner::default();
+    let hall = &runner.hall;
+    let tiers = vec![PriceTier { amount: 0, price: to_yocto("1").into() }];
+    assert!(!call!(runner.root, hall.set_price_tiers(tiers)).is_ok());
+    let tiers = vec![PriceTier { amount: 1, price: to_yocto("1").into() }];
+    assert!(!call!(runner.alice, hall.set_price_tiers(tiers)).is_ok());
+}
@@ -0,0 +1,52 @@
+use crate::*;
+use nft::TokenTrait;
+
+fn token_trait(trait_type: &str, value: &str) -> TokenTrait {
+    TokenTrait { trait_type: trait_type.to_string(), value: value.to_string() }
+}
+
+#[test]
+fn traits_reveal_and_query() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    runner.take_out(3);
+
+    let reveal = vec![
+        ("1".to_string(), vec![token_trait("Background", "Gold"), token_trait("Eyes", "Laser")]),
+        ("2".to_string(), vec![token_trait("Background", "Gray")]),
+        ("3".to_string(), vec![token_trait("Background", "Gray")]),
+    ];
+    assert!(!call!(runner.alice, nft.set_token_traits(reveal.clone())).is_ok());
+    call!(runner.root, nft.set_token_traits(reveal)).assert_success();
+
+    let counts: Vec<(String, u64)> =
+        view!(nft.nft_trait_counts("Background".to_string())).unwrap_json();
+    assert_eq!(counts, vec![("Gold".to_string(), 1), ("Gray".to_string(), 2)]);
+
+    let tokens: Vec<Token> = view!(nft.nft_tokens_by_trait(
+        "Background".to_string(),
+        "Gray".to_string(),
+        Some(U128(1)),
+        Some(10)
+    ))
+    .unwrap_json();
+    assert_eq!(tokens.len(), 1);
+
+    let token: Token = view!(nft.nft_token("1".to_string())).unwrap_json();
+    let extra = token.metadata.unwrap().extra.unwrap();
+    assert!(extra.contains("\"trait_type\":\"Eyes\",\"value\":\"Laser\""));
+
+    let duplicate =
+        vec![("2".to_string(), vec![token_trait("Eyes", "A"), token_trait("Eyes", "B")])];
+    assert!(!call!(runner.root, nft.set_token_traits(duplicate)).is_ok());
+
+    // re-reveal moves the token between values
+    call!(
+        runner.root,
+        nft.set_token_traits(vec![("2".to_string(), vec![token_trait("Background", "Gold")])])
+    )
+    .assert_success();
+    let counts: Vec<(String, u64)> =
+        view!(nft.nft_trait_counts("Background".to_string())).unwrap_json();
+    assert_eq!(counts, vec![("Gray".to_string(), 1), ("Gold".to_string(), 2)]);
+}
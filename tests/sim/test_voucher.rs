@@ -0,0 +1,69 @@
+use crate::*;
+use near_sdk::json_types::U64;
+use nft::MintVoucher;
+
+fn voucher(runner: &Runner, nonce: u64, expires_in: u64) -> MintVoucher {
+    MintVoucher {
+        receiver_id: runner.alice.account_id(),
+        tier: 1,
+        price: U128(to_yocto("1")),
+        expires_at: U64(runner.now() + expires_in * 1_000_000_000),
+        nonce: U64(nonce),
+    }
+}
+
+fn mint(runner: &Runner, voucher: MintVoucher) -> bool {
+    let nft = &runner.nft;
+    let signature = runner.sign_message(voucher.payload(&nft.account_id()));
+    call!(runner.alice, nft.nft_mint_voucher(voucher, signature), deposit = to_yocto("1.1")).is_ok()
+}
+
+#[test]
+fn voucher_mint() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    call!(runner.root, nft.set_voucher_signer(Some(runner.signer_pk()))).assert_success();
+
+    assert!(mint(&runner, voucher(&runner, 1, 60)));
+    assert_eq!(runner.nft_total_supply(), 1);
+    runner.assert_spend_about(&runner.alice, to_yocto("1"));
+    let tier: u8 = view!(nft.nft_tier("1".to_string())).unwrap_json();
+    assert_eq!(tier, 1);
+
+    assert!(!mint(&runner, voucher(&runner, 1, 60)));
+    assert_eq!(runner.nft_total_supply(), 1);
+}
+
+#[test]
+fn voucher_rejected() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    assert!(!mint(&runner, voucher(&runner, 1, 60)));
+
+    call!(runner.root, nft.set_voucher_signer(Some(runner.signer_pk()))).assert_success();
+    let expiring = voucher(&runner, 2, 1);
+    runner.wait(5);
+    assert!(!mint(&runner, expiring));
+
+    let mut tampered = voucher(&runner, 3, 60);
+    let signature = runner.sign_message(tampered.payload(&nft.account_id()));
+    tampered.price = U128(0);
+    assert!(!call!(
+        runner.alice,
+        nft.nft_mint_voucher(tampered, signature),
+        deposit = to_yocto("1.1")
+    )
+    .is_ok());
+    assert_eq!(runner.nft_total_supply(), 0);
+}
+
+#[test]
+fn voucher_signer_owner_only() {
+    let runner = Runner::default();
+    let nft = &runner.nft;
+    call!(runner.root, nft.extend_operators(vec![runner.eva.account_id()]), deposit = 1)
+        .assert_success();
+    assert!(!call!(runner.eva, nft.set_voucher_signer(Some(runner.signer_pk()))).is_ok());
+    assert!(!call!(runner.root, nft.set_voucher_signer(Some("00".to_string()))).is_ok());
+    call!(runner.root, nft.set_voucher_signer(Some(runner.signer_pk()))).assert_success();
+}